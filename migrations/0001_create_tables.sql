@@ -12,7 +12,7 @@ CREATE TABLE IF NOT EXISTS auth (
     issued_tm INTEGER,
     expired_tm INTEGER,
     jwt_id VARCHAR(256),
-    missmatch INTEGER NOT NULL,
+    missmatch INTEGER,
     challenge_at DATETIME,
     login_at DATETIME,
     prev_login_at DATETIME
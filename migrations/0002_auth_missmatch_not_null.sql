@@ -0,0 +1,17 @@
+CREATE TABLE auth_new (
+    account VARCHAR(32) NOT NULL PRIMARY KEY,
+    issued_tm INTEGER,
+    expired_tm INTEGER,
+    jwt_id VARCHAR(256),
+    missmatch INTEGER NOT NULL DEFAULT 0,
+    challenge_at DATETIME,
+    login_at DATETIME,
+    prev_login_at DATETIME
+);
+
+INSERT INTO auth_new (account, issued_tm, expired_tm, jwt_id, missmatch, challenge_at, login_at, prev_login_at)
+SELECT account, issued_tm, expired_tm, jwt_id, COALESCE(missmatch, 0), challenge_at, login_at, prev_login_at FROM auth;
+
+DROP TABLE auth;
+
+ALTER TABLE auth_new RENAME TO auth;
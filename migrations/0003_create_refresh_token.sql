@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS refresh_token (
+    token_hash VARCHAR(64) NOT NULL PRIMARY KEY,
+    family_id VARCHAR(64) NOT NULL,
+    account VARCHAR(32) NOT NULL,
+    expired_at DATETIME NOT NULL,
+    rotated_at DATETIME,
+    revoked_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS refresh_token_family_id ON refresh_token (family_id);
+
+CREATE INDEX IF NOT EXISTS refresh_token_account ON refresh_token (account);
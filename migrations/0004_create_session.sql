@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS session (
+    session_id VARCHAR(64) NOT NULL PRIMARY KEY,
+    account VARCHAR(32) NOT NULL,
+    jwt_id VARCHAR(256) NOT NULL,
+    issued_tm INTEGER NOT NULL,
+    expired_tm INTEGER NOT NULL,
+    user_agent VARCHAR(512),
+    ip_address VARCHAR(64),
+    last_seen_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS session_jwt_id ON session (jwt_id);
+
+CREATE INDEX IF NOT EXISTS session_account ON session (account);
@@ -0,0 +1,3 @@
+ALTER TABLE member ADD COLUMN status VARCHAR(16) NOT NULL DEFAULT 'active';
+
+CREATE INDEX IF NOT EXISTS member_status ON member (status);
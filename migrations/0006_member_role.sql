@@ -0,0 +1,6 @@
+ALTER TABLE member ADD COLUMN role VARCHAR(16) NOT NULL DEFAULT 'member';
+
+UPDATE member SET role = 'admin'
+WHERE account = (
+    SELECT account FROM member WHERE status = 'active' ORDER BY created_at, account LIMIT 1
+);
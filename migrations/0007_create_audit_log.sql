@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS audit_log (
+    audit_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    actor VARCHAR(32) NOT NULL,
+    action VARCHAR(64) NOT NULL,
+    target VARCHAR(32) NOT NULL,
+    detail TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_target ON audit_log (target);
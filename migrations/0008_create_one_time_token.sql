@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS one_time_token (
+    token_hash VARCHAR(64) NOT NULL PRIMARY KEY,
+    account VARCHAR(32) NOT NULL,
+    purpose VARCHAR(32) NOT NULL,
+    expired_at DATETIME NOT NULL,
+    used_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS one_time_token_account ON one_time_token (account, purpose);
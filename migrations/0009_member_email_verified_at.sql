@@ -0,0 +1 @@
+ALTER TABLE member ADD COLUMN email_verified_at DATETIME;
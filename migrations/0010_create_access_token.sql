@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS access_token (
+    token_id VARCHAR(32) NOT NULL PRIMARY KEY,
+    account VARCHAR(32) NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    token_hash VARCHAR(64) NOT NULL UNIQUE,
+    scopes VARCHAR(256) NOT NULL,
+    expired_at DATETIME,
+    last_used_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS access_token_account ON access_token (account);
@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS totp (
+    account VARCHAR(32) NOT NULL PRIMARY KEY,
+    secret VARCHAR(64) NOT NULL,
+    last_step INTEGER NOT NULL DEFAULT 0,
+    confirmed_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS recovery_code (
+    code_hash VARCHAR(64) NOT NULL PRIMARY KEY,
+    account VARCHAR(32) NOT NULL,
+    used_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS recovery_code_account ON recovery_code (account);
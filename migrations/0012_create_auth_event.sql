@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS auth_event (
+    event_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
+    account VARCHAR(32) NOT NULL,
+    event_type VARCHAR(32) NOT NULL,
+    ip_address VARCHAR(64),
+    user_agent VARCHAR(512),
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS auth_event_account ON auth_event (account);
+
+CREATE TRIGGER IF NOT EXISTS auth_event_no_update BEFORE UPDATE ON auth_event
+BEGIN
+    SELECT RAISE(ABORT, 'auth_event is append-only');
+END;
+
+CREATE TRIGGER IF NOT EXISTS auth_event_no_delete BEFORE DELETE ON auth_event
+BEGIN
+    SELECT RAISE(ABORT, 'auth_event is append-only');
+END;
@@ -1,3 +1,10 @@
 pub mod config;
+pub mod crypto;
+pub mod error;
+pub mod jwt;
+pub mod mail;
+pub mod password;
 pub mod setup;
+pub mod totp;
 pub mod types;
+pub mod validation;
@@ -7,6 +7,34 @@ pub static JWT_EXPIRATION_SECONDS: LazyLock<i64> = LazyLock::new(|| {
         .unwrap()
 });
 
+pub static JWT_SIGNING_KEY: LazyLock<Option<String>> =
+    LazyLock::new(|| std::env::var("JWT_SIGNING_KEY").ok());
+
+// a key generated at startup is only allowed where losing every token on restart is fine
+pub static JWT_EPHEMERAL_KEY: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var("JWT_EPHEMERAL_KEY")
+        .map(|v| v.parse().unwrap())
+        .unwrap_or(cfg!(debug_assertions))
+});
+
+pub static JWT_VERIFY_KEYS: LazyLock<Vec<String>> = LazyLock::new(|| {
+    std::env::var("JWT_VERIFY_KEYS")
+        .map(|v| {
+            v.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+});
+
+pub static REFRESH_TOKEN_EXPIRATION_SECONDS: LazyLock<i64> = LazyLock::new(|| {
+    std::env::var("REFRESH_TOKEN_EXPIRATION_SECONDS")
+        .unwrap_or_else(|_| "2592000".to_string())
+        .parse()
+        .unwrap()
+});
+
 pub static MAX_MISSMATCH_COUNT: LazyLock<i32> = LazyLock::new(|| {
     std::env::var("MAX_MISSMATCH_COUNT")
         .unwrap_or_else(|_| "3".to_string())
@@ -21,6 +49,96 @@ pub static LOCK_HOUR: LazyLock<i64> = LazyLock::new(|| {
         .unwrap()
 });
 
+pub static LOGIN_THROTTLE_WINDOW_SECONDS: LazyLock<u64> = LazyLock::new(|| {
+    std::env::var("LOGIN_THROTTLE_WINDOW_SECONDS")
+        .unwrap_or_else(|_| "900".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static LOGIN_THROTTLE_IP_LIMIT: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("LOGIN_THROTTLE_IP_LIMIT")
+        .unwrap_or_else(|_| "20".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static LOGIN_THROTTLE_ACCOUNT_LIMIT: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("LOGIN_THROTTLE_ACCOUNT_LIMIT")
+        .unwrap_or_else(|_| "5".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static LOGIN_THROTTLE_DELAY_SECONDS: LazyLock<u64> = LazyLock::new(|| {
+    std::env::var("LOGIN_THROTTLE_DELAY_SECONDS")
+        .unwrap_or_else(|_| "1".to_string())
+        .parse()
+        .unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub limit: u32,
+    pub window_seconds: u64,
+}
+
+// "10/60" is 10 requests per 60 seconds
+impl std::str::FromStr for RateLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid rate limit: {}", s);
+        let (limit, window_seconds) = s.split_once('/').ok_or_else(invalid)?;
+        let limit = limit.trim().parse().map_err(|_| invalid())?;
+        let window_seconds = window_seconds.trim().parse().map_err(|_| invalid())?;
+        if window_seconds == 0 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            limit,
+            window_seconds,
+        })
+    }
+}
+
+// route=limit/seconds separated by commas, "*" applies to routes without their own entry
+pub static RATE_LIMITS: LazyLock<Vec<(String, RateLimit)>> = LazyLock::new(|| {
+    std::env::var("RATE_LIMITS")
+        .unwrap_or_else(|_| "*=300/60,/service/contents/post=30/60".to_string())
+        .split(',')
+        .filter(|v| !v.trim().is_empty())
+        .map(|v| {
+            let (route, limit) = v
+                .split_once('=')
+                .unwrap_or_else(|| panic!("invalid rate limit: {}", v));
+            (route.trim().to_string(), limit.parse().unwrap())
+        })
+        .collect()
+});
+
+// argon2id parameters for new hashes, older hashes are upgraded on signin
+pub static ARGON2_MEMORY_KIB: LazyLock<u32> = LazyLock::new(|| {
+    std::env::var("ARGON2_MEMORY_KIB")
+        .unwrap_or_else(|_| "19456".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static ARGON2_ITERATIONS: LazyLock<u32> = LazyLock::new(|| {
+    std::env::var("ARGON2_ITERATIONS")
+        .unwrap_or_else(|_| "2".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static ARGON2_PARALLELISM: LazyLock<u32> = LazyLock::new(|| {
+    std::env::var("ARGON2_PARALLELISM")
+        .unwrap_or_else(|_| "1".to_string())
+        .parse()
+        .unwrap()
+});
+
 pub static DB_URL: LazyLock<String> = LazyLock::new(|| {
     std::env::var("DB_URL").unwrap_or_else(|_| "sqlite:./data/database.db".to_string())
 });
@@ -28,11 +146,199 @@ pub static DB_URL: LazyLock<String> = LazyLock::new(|| {
 pub static HOST_NAME: LazyLock<String> =
     LazyLock::new(|| std::env::var("HOST_NAME").unwrap_or_else(|_| "0.0.0.0:3000".to_string()));
 
-pub static SERVE_DIR: LazyLock<Option<String>> =
-    LazyLock::new(|| std::env::var("SERVE_DIR").map(|f| Some(f)).unwrap_or(None));
+pub static SERVE_DIR: LazyLock<Option<String>> = LazyLock::new(|| std::env::var("SERVE_DIR").ok());
 
 pub static CORS_ORIGINS: LazyLock<Option<Vec<String>>> = LazyLock::new(|| {
     std::env::var("CORS_ORIGINS")
         .map(|v| Some(v.split(',').map(|s| s.to_string()).collect()))
         .unwrap_or(None)
 });
+
+pub static TRUST_PROXY_HEADERS: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var("TRUST_PROXY_HEADERS")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse()
+        .unwrap()
+});
+
+// number of reverse proxies that append to X-Forwarded-For in front of this server
+pub static TRUSTED_PROXY_HOPS: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("TRUSTED_PROXY_HOPS")
+        .unwrap_or_else(|_| "1".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static SESSION_COOKIE: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var("SESSION_COOKIE")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static COOKIE_SECURE: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var("COOKIE_SECURE")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse()
+        .unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CookieSameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+impl std::str::FromStr for CookieSameSite {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strict" => Ok(Self::Strict),
+            "lax" => Ok(Self::Lax),
+            "none" => Ok(Self::None),
+            _ => Err(format!("invalid cookie same site: {}", s)),
+        }
+    }
+}
+
+pub static COOKIE_SAME_SITE: LazyLock<CookieSameSite> = LazyLock::new(|| {
+    std::env::var("COOKIE_SAME_SITE")
+        .unwrap_or_else(|_| "strict".to_string())
+        .parse()
+        .unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistrationMode {
+    Open,
+    Approval,
+}
+
+impl std::str::FromStr for RegistrationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" => Ok(Self::Open),
+            "approval" => Ok(Self::Approval),
+            _ => Err(format!("invalid registration mode: {}", s)),
+        }
+    }
+}
+
+pub static REGISTRATION_MODE: LazyLock<RegistrationMode> = LazyLock::new(|| {
+    std::env::var("REGISTRATION_MODE")
+        .unwrap_or_else(|_| "open".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static PASSWORD_RESET_EXPIRATION_SECONDS: LazyLock<i64> = LazyLock::new(|| {
+    std::env::var("PASSWORD_RESET_EXPIRATION_SECONDS")
+        .unwrap_or_else(|_| "3600".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static MAIL_SENDER: LazyLock<String> =
+    LazyLock::new(|| std::env::var("MAIL_SENDER").unwrap_or_else(|_| "log".to_string()));
+
+pub static MAIL_FROM: LazyLock<String> = LazyLock::new(|| {
+    std::env::var("MAIL_FROM").unwrap_or_else(|_| "noreply@localhost".to_string())
+});
+
+pub static MAIL_DIR: LazyLock<String> =
+    LazyLock::new(|| std::env::var("MAIL_DIR").unwrap_or_else(|_| "./data/mail".to_string()));
+
+pub static SMTP_URL: LazyLock<Option<String>> = LazyLock::new(|| std::env::var("SMTP_URL").ok());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailVerificationPolicy {
+    Optional,
+    Signin,
+    Post,
+}
+
+impl EmailVerificationPolicy {
+    pub fn allows_signin(&self, verified: bool) -> bool {
+        verified || *self != Self::Signin
+    }
+
+    pub fn allows_post(&self, verified: bool) -> bool {
+        verified || *self == Self::Optional
+    }
+}
+
+impl std::str::FromStr for EmailVerificationPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "optional" => Ok(Self::Optional),
+            "signin" => Ok(Self::Signin),
+            "post" => Ok(Self::Post),
+            _ => Err(format!("invalid email verification policy: {}", s)),
+        }
+    }
+}
+
+pub static EMAIL_VERIFICATION: LazyLock<EmailVerificationPolicy> = LazyLock::new(|| {
+    std::env::var("EMAIL_VERIFICATION")
+        .unwrap_or_else(|_| "optional".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static EMAIL_VERIFICATION_EXPIRATION_SECONDS: LazyLock<i64> = LazyLock::new(|| {
+    std::env::var("EMAIL_VERIFICATION_EXPIRATION_SECONDS")
+        .unwrap_or_else(|_| "86400".to_string())
+        .parse()
+        .unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentDeletionPolicy {
+    Delete,
+    Reassign,
+}
+
+impl std::str::FromStr for ContentDeletionPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "delete" => Ok(Self::Delete),
+            "reassign" => Ok(Self::Reassign),
+            _ => Err(format!("invalid content deletion policy: {}", s)),
+        }
+    }
+}
+
+pub static ACCOUNT_DELETION_CONTENT: LazyLock<ContentDeletionPolicy> = LazyLock::new(|| {
+    std::env::var("ACCOUNT_DELETION_CONTENT")
+        .unwrap_or_else(|_| "delete".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static TOMBSTONE_ACCOUNT: LazyLock<String> =
+    LazyLock::new(|| std::env::var("TOMBSTONE_ACCOUNT").unwrap_or_else(|_| "deleted".to_string()));
+
+pub static TOTP_ISSUER: LazyLock<String> =
+    LazyLock::new(|| std::env::var("TOTP_ISSUER").unwrap_or_else(|_| "web-api".to_string()));
+
+pub static SECOND_FACTOR_EXPIRATION_SECONDS: LazyLock<i64> = LazyLock::new(|| {
+    std::env::var("SECOND_FACTOR_EXPIRATION_SECONDS")
+        .unwrap_or_else(|_| "300".to_string())
+        .parse()
+        .unwrap()
+});
+
+pub static RECOVERY_CODE_COUNT: LazyLock<usize> = LazyLock::new(|| {
+    std::env::var("RECOVERY_CODE_COUNT")
+        .unwrap_or_else(|_| "10".to_string())
+        .parse()
+        .unwrap()
+});
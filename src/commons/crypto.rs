@@ -0,0 +1,33 @@
+use crate::commons::error::AppError;
+use sha2::{Digest, Sha256};
+
+pub fn generate_token(len: usize) -> Result<String, AppError> {
+    let mut bytes = vec![0u8; len];
+    getrandom::fill(&mut bytes).map_err(AppError::internal)?;
+    Ok(hex::encode(bytes))
+}
+
+pub fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_token() {
+        let token = generate_token(32).unwrap();
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, generate_token(32).unwrap());
+    }
+
+    #[test]
+    fn test_hash_token() {
+        assert_eq!(
+            hash_token("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(hash_token("abc"), hash_token("abd"));
+    }
+}
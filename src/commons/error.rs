@@ -0,0 +1,197 @@
+use crate::commons::validation::FieldError;
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+
+#[derive(Debug)]
+pub enum AppError {
+    NotFound(String),
+    Conflict(String),
+    Validation(Vec<FieldError>),
+    Unauthorized(String),
+    Forbidden(String),
+    Locked(String),
+    TooManyRequests(String, u64),
+    // a request the extractors refused before it reached validation, with their status
+    Rejected(StatusCode, String),
+    Internal(String),
+}
+
+impl AppError {
+    pub fn internal<E: std::fmt::Display>(err: E) -> Self {
+        Self::Internal(err.to_string())
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            Self::NotFound(_) => StatusCode::NOT_FOUND,
+            Self::Conflict(_) => StatusCode::CONFLICT,
+            Self::Validation(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Self::Forbidden(_) => StatusCode::FORBIDDEN,
+            Self::Locked(_) => StatusCode::LOCKED,
+            Self::TooManyRequests(..) => StatusCode::TOO_MANY_REQUESTS,
+            Self::Rejected(status, _) => *status,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    pub fn detail(&self) -> &str {
+        match self {
+            Self::NotFound(detail)
+            | Self::Conflict(detail)
+            | Self::Unauthorized(detail)
+            | Self::Forbidden(detail)
+            | Self::Locked(detail)
+            | Self::TooManyRequests(detail, _)
+            | Self::Rejected(_, detail) => detail,
+            Self::Validation(_) => "validation failed",
+            Self::Internal(_) => "internal server error",
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Internal(detail) => write!(f, "internal server error: {}", detail),
+            _ => write!(f, "{}", self.detail()),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<sqlx::Error> for AppError {
+    fn from(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::RowNotFound => Self::NotFound("not found".to_string()),
+            sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+                Self::Conflict("already exists".to_string())
+            }
+            err => Self::internal(err),
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        if let Self::Internal(detail) = &self {
+            tracing::error!("{}", detail);
+        }
+
+        let mut body = serde_json::json!({
+            "type": "about:blank",
+            "title": status.canonical_reason().unwrap_or_default(),
+            "status": status.as_u16(),
+            "detail": self.detail(),
+        });
+        if let Self::Validation(errors) = &self {
+            body["errors"] = serde_json::json!(errors);
+        }
+
+        let mut response = (
+            status,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(body),
+        )
+            .into_response();
+        if let Self::TooManyRequests(_, retry_after) = &self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(*retry_after));
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_error_status() {
+        assert_eq!(
+            AppError::NotFound("x".to_string()).status(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            AppError::Conflict("x".to_string()).status(),
+            StatusCode::CONFLICT
+        );
+        assert_eq!(
+            AppError::Validation(vec![]).status(),
+            StatusCode::UNPROCESSABLE_ENTITY
+        );
+        assert_eq!(
+            AppError::Unauthorized("x".to_string()).status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            AppError::Forbidden("x".to_string()).status(),
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            AppError::Locked("x".to_string()).status(),
+            StatusCode::LOCKED
+        );
+        assert_eq!(
+            AppError::TooManyRequests("x".to_string(), 1).status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        assert_eq!(
+            AppError::Rejected(StatusCode::UNSUPPORTED_MEDIA_TYPE, "x".to_string()).status(),
+            StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+        assert_eq!(
+            AppError::Internal("x".to_string()).status(),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    #[test]
+    fn test_app_error_hides_internal_detail() {
+        let err = AppError::from(sqlx::Error::PoolTimedOut);
+        assert!(matches!(err, AppError::Internal(_)));
+        assert_eq!(err.detail(), "internal server error");
+
+        let err = AppError::from(sqlx::Error::RowNotFound);
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_app_error_into_response() {
+        let response = AppError::Forbidden("forbidden".to_string()).into_response();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE).unwrap(),
+            "application/problem+json"
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["status"], 403);
+        assert_eq!(body["title"], "Forbidden");
+        assert_eq!(body["detail"], "forbidden");
+
+        let errors = vec![FieldError::new("title", "must not be empty")];
+        let response = AppError::Validation(errors).into_response();
+        assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["errors"][0]["field"], "title");
+        assert_eq!(body["errors"][0]["message"], "must not be empty");
+
+        let response = AppError::TooManyRequests("slow down".to_string(), 30).into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers().get(header::RETRY_AFTER).unwrap(), "30");
+    }
+}
@@ -0,0 +1,152 @@
+use crate::commons::config::{MAIL_DIR, MAIL_FROM, MAIL_SENDER, SMTP_URL};
+use crate::commons::error::AppError;
+use async_trait::async_trait;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor, message::header::ContentType,
+};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mail {
+    pub to: String,
+    pub subject: String,
+    pub body: String,
+}
+
+impl Mail {
+    pub fn new(to: &str, subject: &str, body: &str) -> Self {
+        Self {
+            to: to.to_string(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+pub trait MailSender: Send + Sync {
+    async fn send(&self, mail: Mail) -> Result<(), AppError>;
+}
+
+pub struct LogMailSender;
+
+#[async_trait]
+impl MailSender for LogMailSender {
+    async fn send(&self, mail: Mail) -> Result<(), AppError> {
+        tracing::info!("mail to {}: {}\n{}", mail.to, mail.subject, mail.body);
+        Ok(())
+    }
+}
+
+pub struct FileMailSender {
+    dir: String,
+}
+
+impl FileMailSender {
+    pub fn new(dir: &str) -> Self {
+        Self {
+            dir: dir.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl MailSender for FileMailSender {
+    async fn send(&self, mail: Mail) -> Result<(), AppError> {
+        std::fs::create_dir_all(&self.dir).map_err(AppError::internal)?;
+        let path = std::path::Path::new(&self.dir).join(format!(
+            "{}-{}.txt",
+            chrono::Utc::now().format("%Y%m%d%H%M%S%f"),
+            mail.to.replace(['/', '\\'], "_")
+        ));
+        let content = format!(
+            "From: {}\nTo: {}\nSubject: {}\n\n{}\n",
+            *MAIL_FROM, mail.to, mail.subject, mail.body
+        );
+        std::fs::write(path, content).map_err(AppError::internal)
+    }
+}
+
+pub struct SmtpMailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+}
+
+impl SmtpMailSender {
+    pub fn new(url: &str) -> Result<Self, AppError> {
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::from_url(url)
+            .map_err(AppError::internal)?
+            .build();
+        Ok(Self { transport })
+    }
+}
+
+#[async_trait]
+impl MailSender for SmtpMailSender {
+    async fn send(&self, mail: Mail) -> Result<(), AppError> {
+        let message = Message::builder()
+            .from(MAIL_FROM.parse().map_err(AppError::internal)?)
+            .to(mail.to.parse().map_err(AppError::internal)?)
+            .subject(mail.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(mail.body)
+            .map_err(AppError::internal)?;
+        self.transport
+            .send(message)
+            .await
+            .map_err(AppError::internal)?;
+        Ok(())
+    }
+}
+
+pub fn create_mail_sender() -> Arc<dyn MailSender> {
+    match MAIL_SENDER.as_str() {
+        "smtp" => {
+            let url = SMTP_URL
+                .as_deref()
+                .expect("SMTP_URL is required for MAIL_SENDER=smtp");
+            Arc::new(SmtpMailSender::new(url).unwrap())
+        }
+        "file" => Arc::new(FileMailSender::new(&MAIL_DIR)),
+        _ => Arc::new(LogMailSender),
+    }
+}
+
+#[cfg(test)]
+#[derive(Default)]
+pub struct MemoryMailSender {
+    pub mails: std::sync::Mutex<Vec<Mail>>,
+}
+
+#[cfg(test)]
+#[async_trait]
+impl MailSender for MemoryMailSender {
+    async fn send(&self, mail: Mail) -> Result<(), AppError> {
+        self.mails.lock().unwrap().push(mail);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_mail_sender() {
+        let dir = std::env::temp_dir().join(format!("mail-{}", std::process::id()));
+        let sender = FileMailSender::new(dir.to_str().unwrap());
+
+        let result = sender
+            .send(Mail::new("tester@local", "subject", "body"))
+            .await;
+        assert!(result.is_ok());
+
+        let entries = std::fs::read_dir(&dir).unwrap().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 1);
+
+        let content = std::fs::read_to_string(entries[0].as_ref().unwrap().path()).unwrap();
+        assert!(content.contains("To: tester@local"));
+        assert!(content.contains("Subject: subject"));
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
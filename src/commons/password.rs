@@ -0,0 +1,132 @@
+use crate::commons::config::{ARGON2_ITERATIONS, ARGON2_MEMORY_KIB, ARGON2_PARALLELISM};
+use crate::commons::error::AppError;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use std::sync::LazyLock;
+
+const SALT_LENGTH: usize = 16;
+
+// hashed with the current parameters, so checking an unknown account costs as much as a real one
+pub static DUMMY_HASH: LazyLock<Option<String>> =
+    LazyLock::new(|| params().and_then(|params| hash_with("", params)).ok());
+
+fn params() -> Result<Params, AppError> {
+    Params::new(
+        *ARGON2_MEMORY_KIB,
+        *ARGON2_ITERATIONS,
+        *ARGON2_PARALLELISM,
+        None,
+    )
+    .map_err(AppError::internal)
+}
+
+fn hash_with(password: &str, params: Params) -> Result<String, AppError> {
+    let mut salt = [0u8; SALT_LENGTH];
+    getrandom::fill(&mut salt).map_err(AppError::internal)?;
+    let salt = SaltString::encode_b64(&salt).map_err(AppError::internal)?;
+
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(AppError::internal)?
+        .to_string())
+}
+
+fn verify_with(password: &str, hash: &str) -> Result<bool, AppError> {
+    let hash = PasswordHash::new(hash).map_err(AppError::internal)?;
+    // the parameters are read from the hash itself, so old hashes keep verifying
+    match Argon2::default().verify_password(password.as_bytes(), &hash) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(AppError::internal(e)),
+    }
+}
+
+fn outdated(hash: &str, params: &Params) -> bool {
+    let Ok(hash) = PasswordHash::new(hash) else {
+        return true;
+    };
+    let Ok(current) = Params::try_from(&hash) else {
+        return true;
+    };
+    hash.algorithm != Algorithm::Argon2id.ident()
+        || hash.version != Some(Version::V0x13.into())
+        || current.m_cost() != params.m_cost()
+        || current.t_cost() != params.t_cost()
+        || current.p_cost() != params.p_cost()
+}
+
+// hashing is cpu and memory heavy, so it runs off the async worker threads
+pub async fn hash(password: String) -> Result<String, AppError> {
+    let params = params()?;
+    tokio::task::spawn_blocking(move || hash_with(&password, params))
+        .await
+        .map_err(AppError::internal)?
+}
+
+pub async fn verify(password: String, hash: String) -> Result<bool, AppError> {
+    tokio::task::spawn_blocking(move || verify_with(&password, &hash))
+        .await
+        .map_err(AppError::internal)?
+}
+
+pub async fn verify_dummy(password: String) -> Result<(), AppError> {
+    tokio::task::spawn_blocking(move || match &*DUMMY_HASH {
+        Some(hash) => verify_with(&password, hash).map(|_| ()),
+        None => Ok(()),
+    })
+    .await
+    .map_err(AppError::internal)?
+}
+
+pub fn needs_rehash(hash: &str) -> bool {
+    match params() {
+        Ok(params) => outdated(hash, &params),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_password_hash() {
+        let hash = hash("p@55w0rd".to_string()).await.unwrap();
+        assert!(hash.starts_with("$argon2id$v=19$"));
+        assert!(!needs_rehash(&hash));
+
+        assert!(verify("p@55w0rd".to_string(), hash.clone()).await.unwrap());
+        assert!(!verify("password".to_string(), hash).await.unwrap());
+
+        assert!(
+            verify("x".to_string(), "invalid".to_string())
+                .await
+                .is_err()
+        );
+
+        assert!(verify_dummy("p@55w0rd".to_string()).await.is_ok());
+        assert!(!needs_rehash(DUMMY_HASH.as_ref().unwrap()));
+    }
+
+    #[test]
+    fn test_password_needs_rehash() {
+        let weak = Params::new(16, 1, 1, None).unwrap();
+        let hash = hash_with("p@55w0rd", weak.clone()).unwrap();
+        assert!(verify_with("p@55w0rd", &hash).unwrap());
+
+        assert!(!outdated(&hash, &weak));
+        assert!(outdated(&hash, &Params::new(32, 1, 1, None).unwrap()));
+        assert!(outdated(&hash, &Params::new(16, 2, 1, None).unwrap()));
+        assert!(outdated(&hash, &Params::new(16, 1, 2, None).unwrap()));
+        assert!(needs_rehash(&hash));
+
+        let argon2i = Argon2::new(Algorithm::Argon2i, Version::V0x13, weak.clone())
+            .hash_password(b"p@55w0rd", &SaltString::encode_b64(&[0u8; 16]).unwrap())
+            .unwrap()
+            .to_string();
+        assert!(verify_with("p@55w0rd", &argon2i).unwrap());
+        assert!(outdated(&argon2i, &weak));
+
+        assert!(needs_rehash("invalid"));
+    }
+}
@@ -1,56 +1,66 @@
 use crate::commons::types::{BoxError, DbPool};
-use sqlx::migrate::MigrateDatabase;
+use sqlx::migrate::{Migrate, MigrateDatabase, MigrateError, Migration, Migrator};
 use tracing_subscriber::{EnvFilter, fmt::time::ChronoLocal};
 
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+pub async fn connect_db(db_url: &str) -> Result<DbPool, BoxError> {
+    if db_url != "sqlite::memory:" && !sqlx::Sqlite::database_exists(db_url).await? {
+        sqlx::Sqlite::create_database(db_url).await?;
+    }
+
+    Ok(sqlx::SqlitePool::connect(db_url).await?)
+}
+
 pub async fn initialize_db(db_url: &str) -> Result<DbPool, BoxError> {
-    if db_url != "sqlite::memory:" {
-        if !sqlx::Sqlite::database_exists(db_url).await? {
-            sqlx::Sqlite::create_database(db_url).await?;
-        }
+    let pool = connect_db(db_url).await?;
+
+    MIGRATOR.run(&pool).await?;
+
+    Ok(pool)
+}
+
+pub async fn dry_run_migrations(db_url: &str) -> Result<Vec<&'static Migration>, BoxError> {
+    if db_url != "sqlite::memory:" && !sqlx::Sqlite::database_exists(db_url).await? {
+        return Ok(MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .collect());
     }
 
-    let pool: DbPool = sqlx::SqlitePool::connect(db_url).await?;
+    let pool = connect_db(db_url).await?;
 
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS member (
-account VARCHAR(32) NOT NULL PRIMARY KEY,
-password VARCHAR(512) NOT NULL,
-name VARCHAR(64),
-email VARCHAR(256),
-created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-updated_at DATETIME DEFAULT CURRENT_TIMESTAMP);",
-    )
-    .execute(&pool)
-    .await?;
+    pending_migrations(&pool).await
+}
 
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS auth (
-account VARCHAR(32) NOT NULL PRIMARY KEY,
-issued_tm INTEGER,
-expired_tm INTEGER,
-jwt_id VARCHAR(256),
-missmatch INTEGER,
-challenge_at DATETIME,
-login_at DATETIME,
-prev_login_at DATETIME);",
-    )
-    .execute(&pool)
-    .await?;
+pub async fn pending_migrations(pool: &DbPool) -> Result<Vec<&'static Migration>, BoxError> {
+    let mut executor = pool.acquire().await?;
 
-    sqlx::query(
-        "CREATE TABLE IF NOT EXISTS content (
-content_id INTEGER NOT NULL PRIMARY KEY AUTOINCREMENT,
-account VARCHAR(32) NOT NULL,
-post_at DATETIME NOT NULL,
-title VARCHAR(256) NOT NULL,
-body TEXT NOT NULL,
-created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
-updated_at DATETIME DEFAULT CURRENT_TIMESTAMP);",
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
     )
-    .execute(&pool)
+    .fetch_one(&mut *executor)
     .await?;
 
-    Ok(pool)
+    let applied = match exists {
+        true => executor.list_applied_migrations().await?,
+        false => vec![],
+    };
+
+    for migration in MIGRATOR.iter() {
+        if applied
+            .iter()
+            .any(|a| a.version == migration.version && a.checksum != migration.checksum)
+        {
+            return Err(MigrateError::VersionMismatch(migration.version).into());
+        }
+    }
+
+    Ok(MIGRATOR
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .filter(|migration| !applied.iter().any(|a| a.version == migration.version))
+        .collect())
 }
 
 pub fn init_tracing() {
@@ -65,3 +75,62 @@ pub fn init_tracing() {
         .with_line_number(true)
         .init();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_initialize_db() {
+        let pool = connect_db("sqlite::memory:").await.unwrap();
+
+        let result = pending_migrations(&pool).await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), MIGRATOR.iter().count());
+
+        let result = MIGRATOR.run(&pool).await;
+        assert!(result.is_ok());
+
+        let result = pending_migrations(&pool).await;
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_empty());
+
+        let result = MIGRATOR.run(&pool).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_initialize_legacy_db() {
+        let pool = connect_db("sqlite::memory:").await.unwrap();
+
+        sqlx::query(
+            "CREATE TABLE auth (
+account VARCHAR(32) NOT NULL PRIMARY KEY,
+issued_tm INTEGER,
+expired_tm INTEGER,
+jwt_id VARCHAR(256),
+missmatch INTEGER,
+challenge_at DATETIME,
+login_at DATETIME,
+prev_login_at DATETIME);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query("INSERT INTO auth (account, missmatch) VALUES ('legacy', NULL)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let result = MIGRATOR.run(&pool).await;
+        assert!(result.is_ok());
+
+        let missmatch: i32 =
+            sqlx::query_scalar("SELECT missmatch FROM auth WHERE account = 'legacy'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(missmatch, 0);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::commons::config::TOTP_ISSUER;
+use crate::commons::error::AppError;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+const DIGITS: usize = 6;
+const STEP: u64 = 30;
+const SECRET_LENGTH: usize = 20;
+
+pub fn generate_secret() -> Result<String, AppError> {
+    let mut bytes = vec![0u8; SECRET_LENGTH];
+    getrandom::fill(&mut bytes).map_err(AppError::internal)?;
+    Ok(Secret::Raw(bytes).to_encoded().to_string())
+}
+
+pub fn otpauth_uri(secret: &str, account: &str) -> Result<String, AppError> {
+    Ok(build(secret, account)?.get_url())
+}
+
+pub fn generate(secret: &str, time: u64) -> Result<String, AppError> {
+    Ok(build(secret, "")?.generate(time))
+}
+
+pub fn is_code(value: &str) -> bool {
+    value.len() == DIGITS && value.chars().all(|c| c.is_ascii_digit())
+}
+
+// accepts the previous, current and next step, and returns the matched step only if it is newer than last_step
+pub fn verify(secret: &str, code: &str, last_step: i64) -> Result<Option<i64>, AppError> {
+    let totp = build(secret, "")?;
+    let now = chrono::Utc::now().timestamp() as u64;
+    let current = (now / STEP) as i64;
+    Ok((current - 1..=current + 1)
+        .filter(|step| *step > last_step)
+        .find(|step| totp.check(code, *step as u64 * STEP)))
+}
+
+fn build(secret: &str, account: &str) -> Result<TOTP, AppError> {
+    let secret = Secret::Encoded(secret.to_string())
+        .to_bytes()
+        .map_err(AppError::internal)?;
+    TOTP::new(
+        Algorithm::SHA1,
+        DIGITS,
+        0,
+        STEP,
+        secret,
+        Some(TOTP_ISSUER.clone()),
+        account.to_string(),
+    )
+    .map_err(AppError::internal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totp_verify() {
+        let secret = generate_secret().unwrap();
+        assert_eq!(secret.len(), 32);
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let code = generate(&secret, now).unwrap();
+        assert!(is_code(&code));
+
+        let step = verify(&secret, &code, 0).unwrap();
+        assert_eq!(step, Some((now / STEP) as i64));
+
+        let result = verify(&secret, &code, step.unwrap()).unwrap();
+        assert!(result.is_none());
+
+        let code = generate(&secret, now - STEP * 3).unwrap();
+        assert!(verify(&secret, &code, 0).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_totp_otpauth_uri() {
+        let secret = generate_secret().unwrap();
+        let uri = otpauth_uri(&secret, "tester").unwrap();
+        assert!(uri.starts_with("otpauth://totp/"));
+        assert!(uri.contains(&format!("secret={}", secret)));
+        assert!(uri.contains("tester"));
+
+        assert!(is_code("123456"));
+        assert!(!is_code("12345a"));
+        assert!(!is_code("0123456789"));
+    }
+}
@@ -0,0 +1,134 @@
+use crate::commons::error::AppError;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: &str) -> Self {
+        Self {
+            field: field.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+pub trait Validate {
+    fn validate(&self) -> Result<(), AppError>;
+}
+
+#[derive(Default)]
+pub struct Validator {
+    errors: Vec<FieldError>,
+}
+
+impl Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn check(&mut self, valid: bool, field: &str, message: &str) -> &mut Self {
+        if !valid {
+            self.errors.push(FieldError::new(field, message));
+        }
+        self
+    }
+
+    pub fn length(&mut self, field: &str, value: &str, min: usize, max: usize) -> &mut Self {
+        let len = value.chars().count();
+        if len < min {
+            let message = match min {
+                1 => "must not be empty".to_string(),
+                _ => format!("must be at least {} characters", min),
+            };
+            self.errors.push(FieldError::new(field, &message));
+        } else if len > max {
+            let message = format!("must be at most {} characters", max);
+            self.errors.push(FieldError::new(field, &message));
+        }
+        self
+    }
+
+    pub fn finish(&mut self) -> Result<(), AppError> {
+        match self.errors.is_empty() {
+            true => Ok(()),
+            false => Err(AppError::Validation(std::mem::take(&mut self.errors))),
+        }
+    }
+}
+
+pub fn is_account(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+pub fn is_email(value: &str) -> bool {
+    match value.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.is_empty()
+                && !domain.contains('@')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !value.chars().any(|c| c.is_whitespace() || c.is_control())
+        }
+        None => false,
+    }
+}
+
+pub fn is_strong_password(value: &str) -> bool {
+    value.chars().any(|c| c.is_alphabetic()) && value.chars().any(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator() {
+        let result = Validator::new()
+            .length("account", "", 1, 32)
+            .length("name", &"x".repeat(65), 0, 64)
+            .check(false, "email", "must be a valid email address")
+            .finish();
+
+        match result {
+            Err(AppError::Validation(errors)) => {
+                assert_eq!(errors.len(), 3);
+                assert_eq!(errors[0], FieldError::new("account", "must not be empty"));
+                assert_eq!(
+                    errors[1],
+                    FieldError::new("name", "must be at most 64 characters")
+                );
+                assert_eq!(errors[2].field, "email");
+            }
+            _ => panic!("expected validation error"),
+        }
+
+        let result = Validator::new().length("account", "tester", 1, 32).finish();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_rules() {
+        assert!(is_account("tester_01.dev-x"));
+        assert!(!is_account("tester 01"));
+        assert!(!is_account("テスター"));
+
+        assert!(is_email("tester@local"));
+        assert!(is_email("tester@example.com"));
+        assert!(!is_email("tester"));
+        assert!(!is_email("@example.com"));
+        assert!(!is_email("tester@"));
+        assert!(!is_email("a@b@c"));
+        assert!(!is_email("tes ter@example.com"));
+
+        assert!(is_strong_password("p@55w0rd"));
+        assert!(!is_strong_password("password"));
+        assert!(!is_strong_password("12345678"));
+    }
+}
@@ -1,18 +1,28 @@
 pub mod auth;
 pub mod content;
+pub mod member;
 
 use crate::commons::{
     config::{CORS_ORIGINS, SERVE_DIR},
     types::DbPool,
 };
-use crate::handlers::{auth as auth_handler, content as content_handler};
-use crate::middlewares::auth::{auth_middleware, option_auth_middleware};
+use crate::handlers::{auth as auth_handler, content as content_handler, member as member_handler};
+use crate::middlewares::auth::{
+    auth_middleware, option_auth_middleware, require_role, require_scope, require_session,
+};
+use crate::middlewares::cookie::CSRF_TOKEN_HEADER;
+use crate::middlewares::rate_limit::{
+    RATE_LIMIT_LIMIT, RATE_LIMIT_POLICY, RATE_LIMIT_REMAINING, RATE_LIMIT_RESET, RateLimiter,
+    account_rate_limit, rate_limit,
+};
+use crate::middlewares::throttle::{LoginThrottle, ThrottleCount, login_throttle};
+use crate::models::entities::{access_token::Scope, member::Role};
 use crate::use_cases::Modules;
 use axum::{
     Router,
-    http::{HeaderValue, Method},
+    http::{HeaderName, HeaderValue, Method, header},
     middleware::from_fn_with_state,
-    routing::{any, get, get_service, post},
+    routing::{delete, get, get_service, post},
 };
 use std::sync::Arc;
 use tower_http::{cors::CorsLayer, services::ServeDir};
@@ -20,26 +30,132 @@ use tower_http::{cors::CorsLayer, services::ServeDir};
 pub fn create_handlers(pool: DbPool) -> Router {
     let module = Arc::new(Modules::new(pool));
 
+    let limiter = Arc::new(RateLimiter::from_config());
+    let signin_throttle = Arc::new(LoginThrottle::from_config(ThrottleCount::Failures));
+    let signup_throttle = Arc::new(LoginThrottle::from_config(ThrottleCount::Attempts));
+
     let auth_handler = Router::new()
-        .route("/signin", post(auth_handler::signin))
-        .route("/signup", post(auth_handler::signup));
+        .route(
+            "/signin",
+            post(auth_handler::signin)
+                .layer(from_fn_with_state(signin_throttle.clone(), login_throttle)),
+        )
+        .route(
+            "/signup",
+            post(auth_handler::signup).layer(from_fn_with_state(signup_throttle, login_throttle)),
+        )
+        .route(
+            "/signin/second-factor",
+            post(auth_handler::verify_second_factor)
+                .layer(from_fn_with_state(signin_throttle, login_throttle)),
+        )
+        .route("/refresh", post(auth_handler::refresh))
+        .route(
+            "/email-verification/request",
+            post(auth_handler::request_email_verification),
+        )
+        .route(
+            "/email-verification/confirm",
+            post(auth_handler::confirm_email_verification),
+        )
+        .route(
+            "/password-reset/request",
+            post(auth_handler::request_password_reset),
+        )
+        .route(
+            "/password-reset/confirm",
+            post(auth_handler::confirm_password_reset),
+        )
+        .route_layer(from_fn_with_state(limiter.clone(), rate_limit));
 
     let option_auth_handler = Router::new()
-        .route("/signout", any(auth_handler::signout))
-        .route_layer(from_fn_with_state(module.clone(), option_auth_middleware));
+        .route("/signout", post(auth_handler::signout))
+        .route_layer(from_fn_with_state(module.clone(), option_auth_middleware))
+        .route_layer(from_fn_with_state(limiter.clone(), rate_limit));
+
+    let session_handler = Router::new()
+        .route("/signout-all", post(auth_handler::signout_all))
+        .route("/password", post(auth_handler::change_password))
+        .route("/sessions", get(auth_handler::sessions))
+        .route("/events", get(auth_handler::events))
+        .route(
+            "/sessions/{session_id}",
+            delete(auth_handler::remove_session),
+        )
+        .route(
+            "/tokens",
+            get(auth_handler::access_tokens).post(auth_handler::create_access_token),
+        )
+        .route(
+            "/tokens/{token_id}",
+            delete(auth_handler::remove_access_token),
+        )
+        .route(
+            "/totp",
+            post(auth_handler::enroll_totp).delete(auth_handler::disable_totp),
+        )
+        .route("/totp/confirm", post(auth_handler::confirm_totp))
+        .route_layer(require_session())
+        .route_layer(from_fn_with_state(limiter.clone(), account_rate_limit))
+        .route_layer(from_fn_with_state(module.clone(), auth_middleware))
+        .route_layer(from_fn_with_state(limiter.clone(), rate_limit));
+
+    let auth_handler = auth_handler
+        .merge(option_auth_handler)
+        .merge(session_handler);
 
-    let auth_handler = auth_handler.merge(option_auth_handler);
+    let content_read_handler = Router::new()
+        .route("/", get(content_handler::list))
+        .route("/get/{content_id}", get(content_handler::get))
+        .route_layer(require_scope(Scope::ContentRead));
 
     let content_handler = Router::new()
         .route("/post", post(content_handler::post))
-        .route("/get/{content_id}", get(content_handler::get))
         .route("/edit", post(content_handler::edit))
-        .route("/remove/{content_id}", get(content_handler::remove))
-        .route_layer(from_fn_with_state(module.clone(), auth_middleware));
+        .route("/remove/{content_id}", post(content_handler::remove))
+        .route_layer(require_scope(Scope::ContentWrite))
+        .merge(content_read_handler)
+        .route_layer(from_fn_with_state(limiter.clone(), account_rate_limit))
+        .route_layer(from_fn_with_state(module.clone(), auth_middleware))
+        .route_layer(from_fn_with_state(limiter.clone(), rate_limit));
+
+    // static segments next to /{account} must be listed in RESERVED_ACCOUNTS
+    let profile_handler = Router::new()
+        .route(
+            "/me",
+            get(member_handler::me)
+                .patch(member_handler::update_me)
+                .delete(member_handler::delete_me),
+        )
+        .route("/me/export", get(member_handler::export))
+        .route("/{account}", get(member_handler::profile))
+        .route_layer(require_session())
+        .route_layer(from_fn_with_state(limiter.clone(), account_rate_limit))
+        .route_layer(from_fn_with_state(module.clone(), auth_middleware))
+        .route_layer(from_fn_with_state(limiter.clone(), rate_limit));
+
+    let member_handler = Router::new()
+        .route("/pending", get(member_handler::pending))
+        .route("/auth-events", get(member_handler::auth_events))
+        .route("/{account}", delete(member_handler::delete))
+        .route("/{account}/approve", post(member_handler::approve))
+        .route("/{account}/reject", post(member_handler::reject))
+        .route("/{account}/role", post(member_handler::change_role))
+        .route(
+            "/{account}/lock",
+            get(member_handler::lock_status).delete(member_handler::unlock),
+        )
+        .route_layer(require_role(Role::Admin))
+        .route_layer(require_session())
+        .route_layer(from_fn_with_state(limiter.clone(), account_rate_limit))
+        .route_layer(from_fn_with_state(module.clone(), auth_middleware))
+        .route_layer(from_fn_with_state(limiter.clone(), rate_limit))
+        .merge(profile_handler);
 
     let api = Router::new()
         .nest("/auth", auth_handler)
         .nest("/contents", content_handler)
+        .nest("/members", member_handler)
         .with_state(module);
 
     let api = match &*CORS_ORIGINS {
@@ -47,7 +163,20 @@ pub fn create_handlers(pool: DbPool) -> Router {
             0 => api,
             _ => {
                 let cors = CorsLayer::new()
-                    .allow_methods([Method::GET, Method::POST])
+                    .allow_methods([Method::GET, Method::POST, Method::PATCH, Method::DELETE])
+                    // credentialed requests cannot use wildcards, so the headers are listed explicitly
+                    .allow_headers([
+                        header::AUTHORIZATION,
+                        header::CONTENT_TYPE,
+                        HeaderName::from_static(CSRF_TOKEN_HEADER),
+                    ])
+                    .expose_headers([
+                        header::RETRY_AFTER,
+                        HeaderName::from_static(RATE_LIMIT_LIMIT),
+                        HeaderName::from_static(RATE_LIMIT_REMAINING),
+                        HeaderName::from_static(RATE_LIMIT_RESET),
+                        HeaderName::from_static(RATE_LIMIT_POLICY),
+                    ])
                     .allow_origin(
                         origins
                             .iter()
@@ -62,10 +191,12 @@ pub fn create_handlers(pool: DbPool) -> Router {
         None => api,
     };
 
+    let router = Router::new()
+        .route("/.well-known/jwks.json", get(auth_handler::jwks))
+        .nest("/service", api);
+
     match &*SERVE_DIR {
-        Some(dir) => Router::new()
-            .nest("/service", api)
-            .fallback(get_service(ServeDir::new(dir))),
-        None => Router::new().nest("/service", api),
+        Some(dir) => router.fallback(get_service(ServeDir::new(dir))),
+        None => router,
     }
 }
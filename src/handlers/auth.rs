@@ -1,55 +1,253 @@
+use crate::commons::error::AppError;
+use crate::commons::jwt;
+use crate::middlewares::cookie;
+use crate::middlewares::validation::ValidatedJson;
 use crate::models::dtos::{
-    auth::{SigninDto, SignupDto},
-    member::OptionAuthMemberDto,
+    access_token::AccessTokenCreateDto,
+    auth::{
+        EmailVerificationConfirmDto, EmailVerificationRequestDto, PasswordDto,
+        PasswordResetConfirmDto, PasswordResetRequestDto, RefreshDto, SecondFactorDto, SigninDto,
+        SigninResultDto, SignupDto, TotpCodeDto, TotpDisableDto,
+    },
+    auth_event::AuthEventQueryDto,
+    member::{AuthMemberDto, OptionAuthMemberDto},
+    session::ClientInfoDto,
 };
+use crate::models::entities::member::MemberStatus;
 use crate::use_cases::{Modules, ModulesExt};
 use axum::{
-    extract::{Json, State},
-    http::StatusCode,
+    extract::{Json, Path, Query, State},
+    http::{HeaderMap, Method, StatusCode, header},
     response::IntoResponse,
 };
+use axum_extra::extract::cookie::CookieJar;
 use std::sync::Arc;
 
 pub async fn signup(
     State(modules): State<Arc<Modules>>,
-    Json(dto): Json<SignupDto>,
-) -> impl IntoResponse {
-    let result = modules.auth().signup(dto).await;
-    match result {
-        Ok(_) => StatusCode::OK.into_response(),
-        Err(err) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"message": err.to_string()})),
-        )
-            .into_response(),
-    }
+    ValidatedJson(dto): ValidatedJson<SignupDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let status = match modules.auth().signup(dto).await? {
+        MemberStatus::Pending => StatusCode::ACCEPTED,
+        _ => StatusCode::OK,
+    };
+    Ok(status)
 }
 
 pub async fn signin(
+    client: ClientInfoDto,
+    jar: CookieJar,
     State(modules): State<Arc<Modules>>,
-    Json(dto): Json<SigninDto>,
-) -> impl IntoResponse {
-    let result = modules.auth().signin(dto).await;
-    match result {
-        Ok(token) => (StatusCode::OK, Json(serde_json::json!({"token": token}))).into_response(),
-        Err(err) => (
-            StatusCode::UNAUTHORIZED,
-            Json(serde_json::json!({"message": err.to_string()})),
-        )
-            .into_response(),
+    ValidatedJson(dto): ValidatedJson<SigninDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.auth().signin(dto, client).await?;
+    let (status, jar) = match &dto {
+        SigninResultDto::Token(token) => (StatusCode::OK, cookie::with_session(jar, token)?),
+        SigninResultDto::SecondFactor(_) => (StatusCode::ACCEPTED, jar),
+    };
+    Ok((status, jar, Json(dto)))
+}
+
+pub async fn verify_second_factor(
+    client: ClientInfoDto,
+    jar: CookieJar,
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<SecondFactorDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.auth().verify_second_factor(dto, client).await?;
+    let jar = cookie::with_session(jar, &dto)?;
+    Ok((StatusCode::OK, jar, Json(dto)))
+}
+
+// browsers send {} and the refresh token comes from the cookie
+pub async fn refresh(
+    client: ClientInfoDto,
+    method: Method,
+    headers: HeaderMap,
+    jar: CookieJar,
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(mut dto): ValidatedJson<RefreshDto>,
+) -> Result<impl IntoResponse, AppError> {
+    if dto.refresh_token.is_empty() {
+        dto.refresh_token = cookie::refresh_token(&jar)
+            .ok_or(AppError::Unauthorized("missing refresh token".to_string()))?;
+        cookie::verify_csrf(&method, &headers, &jar)?;
     }
+    let dto = modules.auth().refresh(dto, client).await?;
+    let jar = cookie::with_session(jar, &dto)?;
+    Ok((StatusCode::OK, jar, Json(dto)))
 }
 
 pub async fn signout(
+    client: ClientInfoDto,
     option_autn_member: OptionAuthMemberDto,
+    jar: CookieJar,
     State(modules): State<Arc<Modules>>,
 ) -> impl IntoResponse {
+    let jar = cookie::without_session(jar);
+
     if option_autn_member.auth_member.is_none() {
-        return StatusCode::OK.into_response();
+        return (StatusCode::OK, jar).into_response();
     }
     let auth_member = option_autn_member.auth_member.unwrap();
 
-    let _ = modules.auth().signout(&auth_member.account).await;
+    let _ = modules.auth().signout(&auth_member, client).await;
+
+    (StatusCode::OK, jar).into_response()
+}
+
+pub async fn signout_all(
+    client: ClientInfoDto,
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+) -> Result<impl IntoResponse, AppError> {
+    modules
+        .auth()
+        .signout_all(&autn_member.account, client)
+        .await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn change_password(
+    client: ClientInfoDto,
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<PasswordDto>,
+) -> Result<impl IntoResponse, AppError> {
+    modules
+        .auth()
+        .change_password(&autn_member, dto, client)
+        .await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn request_email_verification(
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<EmailVerificationRequestDto>,
+) -> Result<impl IntoResponse, AppError> {
+    modules.auth().request_email_verification(dto).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn confirm_email_verification(
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<EmailVerificationConfirmDto>,
+) -> Result<impl IntoResponse, AppError> {
+    modules.auth().confirm_email_verification(dto).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn request_password_reset(
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<PasswordResetRequestDto>,
+) -> Result<impl IntoResponse, AppError> {
+    modules.auth().request_password_reset(dto).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn confirm_password_reset(
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<PasswordResetConfirmDto>,
+) -> Result<impl IntoResponse, AppError> {
+    modules.auth().confirm_password_reset(dto).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn sessions(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+) -> Result<impl IntoResponse, AppError> {
+    let sessions = modules.auth().sessions(&autn_member).await?;
+    Ok((StatusCode::OK, Json(sessions)))
+}
+
+pub async fn events(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Query(dto): Query<AuthEventQueryDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.auth().auth_events(&autn_member, dto).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn remove_session(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Path(session_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    modules
+        .auth()
+        .remove_session(&autn_member.account, &session_id)
+        .await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn access_tokens(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.auth().access_tokens(&autn_member).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn create_access_token(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<AccessTokenCreateDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules
+        .auth()
+        .create_access_token(&autn_member, dto)
+        .await?;
+    Ok((StatusCode::CREATED, Json(dto)))
+}
+
+pub async fn remove_access_token(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Path(token_id): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    modules
+        .auth()
+        .remove_access_token(&autn_member.account, &token_id)
+        .await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn enroll_totp(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.auth().enroll_totp(&autn_member).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn confirm_totp(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<TotpCodeDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.auth().confirm_totp(&autn_member, dto).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn disable_totp(
+    client: ClientInfoDto,
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<TotpDisableDto>,
+) -> Result<impl IntoResponse, AppError> {
+    modules
+        .auth()
+        .disable_totp(&autn_member, dto, client)
+        .await?;
+    Ok(StatusCode::OK)
+}
 
-    StatusCode::OK.into_response()
+pub async fn jwks() -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [(header::CACHE_CONTROL, "public, max-age=300")],
+        Json(jwt::jwks()),
+    )
 }
@@ -1,89 +1,63 @@
-use crate::models::dtos::{content::ContentDto, member::AuthMemberDto};
+use crate::commons::error::AppError;
+use crate::middlewares::validation::ValidatedJson;
+use crate::models::dtos::{
+    content::{ContentDto, ContentQueryDto},
+    member::AuthMemberDto,
+};
 use crate::use_cases::{Modules, ModulesExt};
 use axum::{
-    extract::{Json, Path, State},
+    extract::{Json, OriginalUri, Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
 };
 use std::sync::Arc;
 
 pub async fn post(
-    _autn_member: AuthMemberDto,
+    autn_member: AuthMemberDto,
     State(modules): State<Arc<Modules>>,
-    Json(dto): Json<ContentDto>,
-) -> impl IntoResponse {
-    let result = modules.content().post(dto).await;
-    match result {
-        Ok(entity) => (StatusCode::OK, Json(entity)).into_response(),
-        Err(err) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"message": err.to_string()})),
-        )
-            .into_response(),
-    }
+    ValidatedJson(dto): ValidatedJson<ContentDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.content().post(&autn_member, dto).await?;
+    Ok((StatusCode::OK, Json(dto)))
 }
 
 pub async fn get(
     _autn_member: AuthMemberDto,
     State(modules): State<Arc<Modules>>,
     Path(content_id): Path<i64>,
-) -> impl IntoResponse {
-    let result = modules.content().get(content_id).await;
-    match result {
-        Ok(entity) => match entity {
-            Some(entity) => (StatusCode::OK, Json(entity)).into_response(),
-            None => (
-                StatusCode::NOT_FOUND,
-                Json(serde_json::json!({"message": "not found"})),
-            )
-                .into_response(),
-        },
-        Err(err) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"message": err.to_string()})),
-        )
-            .into_response(),
-    }
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules
+        .content()
+        .get(content_id)
+        .await?
+        .ok_or(AppError::NotFound("content not found".to_string()))?;
+    Ok((StatusCode::OK, Json(dto)))
 }
 
 pub async fn edit(
-    _autn_member: AuthMemberDto,
+    autn_member: AuthMemberDto,
     State(modules): State<Arc<Modules>>,
-    Json(dto): Json<ContentDto>,
-) -> impl IntoResponse {
-    let result = modules.content().edit(dto).await;
-    match result {
-        Ok(entity) => (StatusCode::OK, Json(entity)).into_response(),
-        Err(err) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"message": err.to_string()})),
-        )
-            .into_response(),
-    }
+    ValidatedJson(dto): ValidatedJson<ContentDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.content().edit(&autn_member, dto).await?;
+    Ok((StatusCode::OK, Json(dto)))
 }
 
 pub async fn remove(
-    _autn_member: AuthMemberDto,
+    autn_member: AuthMemberDto,
     State(modules): State<Arc<Modules>>,
     Path(content_id): Path<i64>,
-) -> impl IntoResponse {
-    let result = modules.content().remove(content_id).await;
-    match result {
-        Ok(count) => {
-            if count == 0 {
-                (
-                    StatusCode::NOT_FOUND,
-                    Json(serde_json::json!({"message": "not found"})),
-                )
-                    .into_response()
-            } else {
-                (StatusCode::OK).into_response()
-            }
-        }
-        Err(err) => (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(serde_json::json!({"message": err.to_string()})),
-        )
-            .into_response(),
-    }
+) -> Result<impl IntoResponse, AppError> {
+    modules.content().remove(&autn_member, content_id).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn list(
+    _autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    OriginalUri(uri): OriginalUri,
+    Query(dto): Query<ContentQueryDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let page = modules.content().list(dto).await?;
+    Ok((StatusCode::OK, Json(page.with_links(uri.path()))))
 }
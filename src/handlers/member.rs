@@ -0,0 +1,137 @@
+use crate::commons::error::AppError;
+use crate::middlewares::validation::ValidatedJson;
+use crate::models::dtos::{
+    auth_event::AuthEventQueryDto,
+    member::{AccountDeletionDto, AuthMemberDto, ProfileDto, RoleDto},
+    session::ClientInfoDto,
+};
+use crate::use_cases::{Modules, ModulesExt};
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
+use std::sync::Arc;
+
+pub async fn me(autn_member: AuthMemberDto) -> Result<impl IntoResponse, AppError> {
+    Ok((StatusCode::OK, Json(autn_member)))
+}
+
+pub async fn update_me(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<ProfileDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.member().update_profile(&autn_member, dto).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn delete_me(
+    client: ClientInfoDto,
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    ValidatedJson(dto): ValidatedJson<AccountDeletionDto>,
+) -> Result<impl IntoResponse, AppError> {
+    modules.member().withdraw(&autn_member, dto, client).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn export(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.member().export(&autn_member).await?;
+    let disposition = format!(
+        "attachment; filename=\"{}-export.json\"",
+        autn_member.account
+    );
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_DISPOSITION, disposition)],
+        Json(dto),
+    ))
+}
+
+pub async fn profile(
+    _autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Path(account): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.member().profile(&account).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn pending(
+    _autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.member().pending().await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn auth_events(
+    _autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Query(dto): Query<AuthEventQueryDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.auth().search_auth_events(dto).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn approve(
+    _autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Path(account): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.member().approve(&account).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn reject(
+    _autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Path(account): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    modules.member().reject(&account).await?;
+    Ok(StatusCode::OK)
+}
+
+pub async fn change_role(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Path(account): Path<String>,
+    ValidatedJson(dto): ValidatedJson<RoleDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules
+        .member()
+        .change_role(&autn_member, &account, dto.role)
+        .await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn lock_status(
+    _autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Path(account): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.member().lock_status(&account).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn unlock(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Path(account): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    let dto = modules.member().unlock(&autn_member, &account).await?;
+    Ok((StatusCode::OK, Json(dto)))
+}
+
+pub async fn delete(
+    autn_member: AuthMemberDto,
+    State(modules): State<Arc<Modules>>,
+    Path(account): Path<String>,
+) -> Result<impl IntoResponse, AppError> {
+    modules.member().delete(&autn_member, &account).await?;
+    Ok(StatusCode::OK)
+}
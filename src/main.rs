@@ -1,7 +1,10 @@
+use std::net::SocketAddr;
 use tokio::net::TcpListener;
-use web_api::commons::setup::initialize_db;
+use web_api::commons::setup::{dry_run_migrations, initialize_db};
 use web_api::commons::{
     config::{DB_URL, HOST_NAME},
+    jwt::JWT_KEYS,
+    password::DUMMY_HASH,
     setup,
     types::{BoxError, DbPool},
 };
@@ -11,16 +14,40 @@ use web_api::handlers::create_handlers;
 async fn main() -> Result<(), BoxError> {
     setup::init_tracing();
 
-    let pool: DbPool = initialize_db(&*DB_URL).await?;
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.iter().any(|arg| arg == "--dry-run") {
+        let pending = dry_run_migrations(&DB_URL).await?;
+        if pending.is_empty() {
+            println!("no pending migrations");
+        }
+        for migration in pending {
+            println!("pending: {} {}", migration.version, migration.description);
+        }
+        return Ok(());
+    }
+
+    let pool: DbPool = initialize_db(&DB_URL).await?;
+
+    if args.iter().any(|arg| arg == "--migrate-only") {
+        println!("migrations applied");
+        return Ok(());
+    }
+
+    std::sync::LazyLock::force(&JWT_KEYS);
+    std::sync::LazyLock::force(&DUMMY_HASH);
 
     let app = create_handlers(pool);
 
     let listener = TcpListener::bind(&*HOST_NAME).await?;
     tracing::info!("Listening on {}", listener.local_addr()?);
 
-    axum::serve(listener, app.into_make_service())
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(shutdown_signal())
+    .await?;
 
     Ok(())
 }
@@ -1 +1,6 @@
 pub mod auth;
+pub mod client;
+pub mod cookie;
+pub mod rate_limit;
+pub mod throttle;
+pub mod validation;
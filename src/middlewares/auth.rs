@@ -1,50 +1,154 @@
+use crate::commons::error::AppError;
+use crate::middlewares::cookie;
 use crate::models::dtos::member::{AuthMemberDto, OptionAuthMemberDto};
+use crate::models::dtos::session::ClientInfoDto;
+use crate::models::entities::access_token::{ACCESS_TOKEN_PREFIX, Scope};
+use crate::models::entities::member::Role;
 use crate::use_cases::Modules;
-use axum::middleware::Next;
+use axum::middleware::{FromFnLayer, Next, from_fn};
 use axum::{
     RequestExt,
     extract::{FromRequestParts, Request, State},
-    http::{StatusCode, request::Parts},
+    http::request::Parts,
     response::Response,
 };
 use axum_extra::{
     TypedHeader,
+    extract::cookie::CookieJar,
     headers::{Authorization, authorization::Bearer},
 };
-use std::sync::Arc;
+use std::{future::Future, pin::Pin, sync::Arc};
+
+type RequireFuture = Pin<Box<dyn Future<Output = Result<Response, AppError>> + Send>>;
 
 impl<S> FromRequestParts<S> for AuthMemberDto
 where
     S: Send + Sync,
 {
-    type Rejection = StatusCode;
+    type Rejection = AppError;
 
     async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
         let auth_member = parts
             .extensions
             .get::<Self>()
-            .ok_or(StatusCode::UNAUTHORIZED)?;
+            .ok_or(AppError::Unauthorized("unauthorized".to_string()))?;
 
         Ok(auth_member.clone())
     }
 }
 
+pub fn require_role(
+    role: Role,
+) -> FromFnLayer<
+    impl Fn(AuthMemberDto, Request, Next) -> RequireFuture + Clone + Send + 'static,
+    (),
+    (AuthMemberDto, Request),
+> {
+    require(move |auth_member| {
+        if !auth_member.has_role(role) {
+            return Err(AppError::Forbidden("insufficient role".to_string()));
+        }
+        Ok(())
+    })
+}
+
+pub fn require_scope(
+    scope: Scope,
+) -> FromFnLayer<
+    impl Fn(AuthMemberDto, Request, Next) -> RequireFuture + Clone + Send + 'static,
+    (),
+    (AuthMemberDto, Request),
+> {
+    require(move |auth_member| {
+        if !auth_member.has_scope(scope) {
+            return Err(AppError::Forbidden("insufficient scope".to_string()));
+        }
+        Ok(())
+    })
+}
+
+// access tokens are limited to their scopes, account and session management needs a signed-in session
+pub fn require_session() -> FromFnLayer<
+    impl Fn(AuthMemberDto, Request, Next) -> RequireFuture + Clone + Send + 'static,
+    (),
+    (AuthMemberDto, Request),
+> {
+    require(|auth_member| {
+        if !auth_member.is_session() {
+            return Err(AppError::Forbidden(
+                "access token is not allowed".to_string(),
+            ));
+        }
+        Ok(())
+    })
+}
+
+fn require<F>(
+    check: F,
+) -> FromFnLayer<
+    impl Fn(AuthMemberDto, Request, Next) -> RequireFuture + Clone + Send + 'static,
+    (),
+    (AuthMemberDto, Request),
+>
+where
+    F: Fn(&AuthMemberDto) -> Result<(), AppError> + Clone + Send + Sync + 'static,
+{
+    from_fn(
+        move |auth_member: AuthMemberDto, request: Request, next: Next| {
+            let result = check(&auth_member);
+            Box::pin(async move {
+                result?;
+                Ok(next.run(request).await)
+            }) as RequireFuture
+        },
+    )
+}
+
+enum Credential {
+    Bearer(String),
+    Cookie(String),
+}
+
+// the bearer header wins, the session cookie is only used without it and needs a csrf token on unsafe methods
+async fn credential(request: &mut Request) -> Result<Option<Credential>, AppError> {
+    if let Ok(bearer) = request
+        .extract_parts::<TypedHeader<Authorization<Bearer>>>()
+        .await
+    {
+        return Ok(Some(Credential::Bearer(bearer.token().to_string())));
+    }
+
+    let jar = CookieJar::from_headers(request.headers());
+    match cookie::access_token(&jar) {
+        Some(token) => {
+            cookie::verify_csrf(request.method(), request.headers(), &jar)?;
+            Ok(Some(Credential::Cookie(token)))
+        }
+        None => Ok(None),
+    }
+}
+
 pub async fn auth_middleware(
     State(module): State<Arc<Modules>>,
     mut request: Request,
     next: Next,
-) -> axum::response::Result<Response> {
-    let bearer = request
-        .extract_parts::<TypedHeader<Authorization<Bearer>>>()
-        .await
-        .map_err(|_| StatusCode::BAD_REQUEST)?;
-    let token = bearer.token();
+) -> Result<Response, AppError> {
+    let credential = credential(&mut request)
+        .await?
+        .ok_or(AppError::Unauthorized("missing bearer token".to_string()))?;
+    let client = request.extract_parts::<ClientInfoDto>().await?;
 
-    let auth_member = module
-        .auth
-        .authenticate(token)
-        .await
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let auth_member = match credential {
+        Credential::Bearer(token) if token.starts_with(ACCESS_TOKEN_PREFIX) => {
+            module
+                .auth
+                .authenticate_access_token(&token, client)
+                .await?
+        }
+        Credential::Bearer(token) | Credential::Cookie(token) => {
+            module.auth.authenticate(&token, client).await?
+        }
+    };
 
     request.extensions_mut().insert(auth_member.clone());
 
@@ -55,7 +159,7 @@ impl<S> FromRequestParts<S> for OptionAuthMemberDto
 where
     S: Send + Sync,
 {
-    type Rejection = StatusCode;
+    type Rejection = AppError;
 
     async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
         let auth_member = parts
@@ -71,25 +175,21 @@ pub async fn option_auth_middleware(
     State(module): State<Arc<Modules>>,
     mut request: Request,
     next: Next,
-) -> axum::response::Result<Response> {
-    let bearer = request
-        .extract_parts::<TypedHeader<Authorization<Bearer>>>()
-        .await;
+) -> Result<Response, AppError> {
+    // a failed csrf check is an error, not a missing credential, so a forged signout is refused
+    let credential = credential(&mut request).await?;
+    let client = request.extract_parts::<ClientInfoDto>().await?;
 
-    let auth_member = match bearer {
-        Ok(bearer) => {
-            let token = bearer.token();
-
-            module
-                .auth
-                .authenticate(token)
-                .await
-                .map(|auth_member| OptionAuthMemberDto {
-                    auth_member: Some(auth_member),
-                })
-                .unwrap_or(OptionAuthMemberDto { auth_member: None })
-        }
-        Err(_) => OptionAuthMemberDto { auth_member: None },
+    let auth_member = match credential {
+        Some(Credential::Bearer(token) | Credential::Cookie(token)) => module
+            .auth
+            .authenticate(&token, client)
+            .await
+            .map(|auth_member| OptionAuthMemberDto {
+                auth_member: Some(auth_member),
+            })
+            .unwrap_or(OptionAuthMemberDto { auth_member: None }),
+        None => OptionAuthMemberDto { auth_member: None },
     };
 
     request.extensions_mut().insert(auth_member.clone());
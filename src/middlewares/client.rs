@@ -0,0 +1,120 @@
+use crate::commons::config::{TRUST_PROXY_HEADERS, TRUSTED_PROXY_HOPS};
+use crate::commons::error::AppError;
+use crate::models::dtos::session::ClientInfoDto;
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::{HeaderMap, header, request::Parts},
+};
+use std::net::SocketAddr;
+
+const MAX_USER_AGENT_LENGTH: usize = 512;
+
+impl<S> FromRequestParts<S> for ClientInfoDto
+where
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        let user_agent = parts
+            .headers
+            .get(header::USER_AGENT)
+            .and_then(|value| value.to_str().ok())
+            .map(|value| value.chars().take(MAX_USER_AGENT_LENGTH).collect());
+
+        let ip_address = match *TRUST_PROXY_HEADERS {
+            true => forwarded_for(&parts.headers, *TRUSTED_PROXY_HOPS),
+            false => None,
+        }
+        .or_else(|| {
+            parts
+                .extensions
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip().to_string())
+        });
+
+        Ok(Self {
+            user_agent,
+            ip_address,
+        })
+    }
+}
+
+// entries left of the ones added by our own proxies are sent by the client and cannot be trusted
+fn forwarded_for(headers: &HeaderMap, hops: usize) -> Option<String> {
+    let entries = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|value| value.trim())
+        .collect::<Vec<_>>();
+
+    entries
+        .len()
+        .checked_sub(hops.max(1))
+        .map(|index| entries[index].to_string())
+        .filter(|value| !value.is_empty())
+}
+
+// limits keyed on the address must not put every unknown client into one shared bucket
+pub fn require_ip_address(client: ClientInfoDto) -> Result<String, AppError> {
+    client.ip_address.ok_or(AppError::Internal(
+        "client address is unavailable".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request};
+
+    #[tokio::test]
+    async fn test_client_info() {
+        let request = Request::builder()
+            .header(header::USER_AGENT, "curl/8.0")
+            .header("x-forwarded-for", "203.0.113.1, 10.0.0.1")
+            .body(Body::empty())
+            .unwrap();
+        let (mut parts, _) = request.into_parts();
+        parts
+            .extensions
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 3000))));
+
+        let client = ClientInfoDto::from_request_parts(&mut parts, &()).await;
+        assert!(client.is_ok());
+
+        let client = client.unwrap();
+        assert_eq!(client.user_agent, Some("curl/8.0".to_string()));
+        assert_eq!(client.ip_address, Some("127.0.0.1".to_string()));
+
+        assert_eq!(
+            forwarded_for(&parts.headers, 1),
+            Some("10.0.0.1".to_string())
+        );
+        assert_eq!(
+            forwarded_for(&parts.headers, 2),
+            Some("203.0.113.1".to_string())
+        );
+        assert_eq!(forwarded_for(&parts.headers, 3), None);
+    }
+
+    #[test]
+    fn test_client_info_spoofed_forwarded_for() {
+        let request = Request::builder()
+            .header("x-forwarded-for", "1.2.3.4, 5.6.7.8")
+            .header("x-forwarded-for", "198.51.100.7")
+            .body(Body::empty())
+            .unwrap();
+        let (parts, _) = request.into_parts();
+
+        // the proxy appended the real peer, whatever the client put in front of it
+        assert_eq!(
+            forwarded_for(&parts.headers, 1),
+            Some("198.51.100.7".to_string())
+        );
+        assert_eq!(forwarded_for(&HeaderMap::new(), 1), None);
+
+        assert!(require_ip_address(ClientInfoDto::default()).is_err());
+    }
+}
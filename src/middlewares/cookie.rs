@@ -0,0 +1,143 @@
+use crate::commons::config::{
+    COOKIE_SAME_SITE, COOKIE_SECURE, CookieSameSite, JWT_EXPIRATION_SECONDS,
+    REFRESH_TOKEN_EXPIRATION_SECONDS, SESSION_COOKIE,
+};
+use crate::commons::crypto::{generate_token, hash_token};
+use crate::commons::error::AppError;
+use crate::models::dtos::auth::TokenDto;
+use axum::http::{HeaderMap, Method};
+use axum_extra::extract::cookie::{Cookie, CookieJar, SameSite};
+
+pub const ACCESS_TOKEN_COOKIE: &str = "access_token";
+
+pub const REFRESH_TOKEN_COOKIE: &str = "refresh_token";
+
+pub const CSRF_TOKEN_COOKIE: &str = "csrf_token";
+
+pub const CSRF_TOKEN_HEADER: &str = "x-csrf-token";
+
+// the refresh token is only sent to /auth/refresh and /auth/signout
+const REFRESH_TOKEN_PATH: &str = "/service/auth";
+
+// the csrf cookie is readable by the SPA, which sends it back in the X-CSRF-Token header
+pub fn with_session(jar: CookieJar, token: &TokenDto) -> Result<CookieJar, AppError> {
+    if !*SESSION_COOKIE {
+        return Ok(jar);
+    }
+    Ok(jar
+        .add(build(
+            ACCESS_TOKEN_COOKIE,
+            &token.token,
+            "/",
+            *JWT_EXPIRATION_SECONDS,
+            true,
+        ))
+        .add(build(
+            REFRESH_TOKEN_COOKIE,
+            &token.refresh_token,
+            REFRESH_TOKEN_PATH,
+            *REFRESH_TOKEN_EXPIRATION_SECONDS,
+            true,
+        ))
+        .add(build(
+            CSRF_TOKEN_COOKIE,
+            &generate_token(32)?,
+            "/",
+            *REFRESH_TOKEN_EXPIRATION_SECONDS,
+            false,
+        )))
+}
+
+pub fn without_session(jar: CookieJar) -> CookieJar {
+    if !*SESSION_COOKIE {
+        return jar;
+    }
+    jar.remove(Cookie::build(ACCESS_TOKEN_COOKIE).path("/"))
+        .remove(Cookie::build(REFRESH_TOKEN_COOKIE).path(REFRESH_TOKEN_PATH))
+        .remove(Cookie::build(CSRF_TOKEN_COOKIE).path("/"))
+}
+
+pub fn access_token(jar: &CookieJar) -> Option<String> {
+    match *SESSION_COOKIE {
+        true => value(jar, ACCESS_TOKEN_COOKIE),
+        false => None,
+    }
+}
+
+pub fn refresh_token(jar: &CookieJar) -> Option<String> {
+    match *SESSION_COOKIE {
+        true => value(jar, REFRESH_TOKEN_COOKIE),
+        false => None,
+    }
+}
+
+// double submit: a cross-site request carries the cookies but cannot read them to set the header
+pub fn verify_csrf(method: &Method, headers: &HeaderMap, jar: &CookieJar) -> Result<(), AppError> {
+    if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        return Ok(());
+    }
+    let header = headers
+        .get(CSRF_TOKEN_HEADER)
+        .and_then(|value| value.to_str().ok());
+    match (header, value(jar, CSRF_TOKEN_COOKIE)) {
+        // digests are compared so the timing does not depend on the matching prefix
+        (Some(header), Some(cookie)) if hash_token(header) == hash_token(&cookie) => Ok(()),
+        _ => Err(AppError::Forbidden("invalid csrf token".to_string())),
+    }
+}
+
+fn value(jar: &CookieJar, name: &str) -> Option<String> {
+    jar.get(name)
+        .map(|cookie| cookie.value().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn build(name: &str, value: &str, path: &str, max_age: i64, http_only: bool) -> Cookie<'static> {
+    let same_site = match *COOKIE_SAME_SITE {
+        CookieSameSite::Strict => SameSite::Strict,
+        CookieSameSite::Lax => SameSite::Lax,
+        CookieSameSite::None => SameSite::None,
+    };
+    Cookie::build((name.to_string(), value.to_string()))
+        .path(path.to_string())
+        .max_age(time::Duration::seconds(max_age))
+        .http_only(http_only)
+        // browsers reject SameSite=None cookies without Secure
+        .secure(*COOKIE_SECURE || same_site == SameSite::None)
+        .same_site(same_site)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_verify_csrf() {
+        let jar = CookieJar::new().add(Cookie::new(CSRF_TOKEN_COOKIE, "csrf"));
+        let mut headers = HeaderMap::new();
+
+        assert!(verify_csrf(&Method::GET, &headers, &jar).is_ok());
+        assert!(matches!(
+            verify_csrf(&Method::POST, &headers, &jar),
+            Err(AppError::Forbidden(_))
+        ));
+
+        headers.insert(CSRF_TOKEN_HEADER, HeaderValue::from_static("other"));
+        assert!(verify_csrf(&Method::DELETE, &headers, &jar).is_err());
+
+        headers.insert(CSRF_TOKEN_HEADER, HeaderValue::from_static("csrf"));
+        assert!(verify_csrf(&Method::POST, &headers, &jar).is_ok());
+        assert!(verify_csrf(&Method::POST, &headers, &CookieJar::new()).is_err());
+    }
+
+    #[test]
+    fn test_build_cookie() {
+        let cookie = build(ACCESS_TOKEN_COOKIE, "token", "/", 60, true);
+        assert_eq!(cookie.http_only(), Some(true));
+        assert_eq!(cookie.secure(), Some(true));
+        assert_eq!(cookie.same_site(), Some(SameSite::Strict));
+        assert_eq!(cookie.max_age(), Some(time::Duration::seconds(60)));
+    }
+}
@@ -0,0 +1,395 @@
+use crate::commons::config::{RATE_LIMITS, RateLimit};
+use crate::commons::error::AppError;
+use crate::middlewares::client::require_ip_address;
+use crate::models::dtos::member::AuthMemberDto;
+use crate::models::dtos::session::ClientInfoDto;
+use axum::{
+    RequestExt,
+    extract::{MatchedPath, Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const SWEEP_SECONDS: u64 = 60;
+
+pub const RATE_LIMIT_LIMIT: &str = "ratelimit-limit";
+pub const RATE_LIMIT_REMAINING: &str = "ratelimit-remaining";
+pub const RATE_LIMIT_RESET: &str = "ratelimit-reset";
+pub const RATE_LIMIT_POLICY: &str = "ratelimit-policy";
+
+struct Bucket {
+    tokens: f64,
+    capacity: f64,
+    rate: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn new(policy: RateLimit, now: Instant) -> Self {
+        let capacity = policy.limit as f64;
+        Self {
+            tokens: capacity,
+            capacity,
+            rate: capacity / policy.window_seconds as f64,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.updated_at = now;
+    }
+
+    fn seconds_until(&self, tokens: f64) -> u64 {
+        ((tokens - self.tokens).max(0.0) / self.rate).ceil() as u64
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitStatus {
+    pub policy: RateLimit,
+    pub remaining: u32,
+    pub reset: u64,
+    pub retry_after: Option<u64>,
+}
+
+impl RateLimitStatus {
+    fn headers(&self) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in [
+            (RATE_LIMIT_LIMIT, self.policy.limit.to_string()),
+            (RATE_LIMIT_REMAINING, self.remaining.to_string()),
+            (RATE_LIMIT_RESET, self.reset.to_string()),
+            (
+                RATE_LIMIT_POLICY,
+                format!("{};w={}", self.policy.limit, self.policy.window_seconds),
+            ),
+        ] {
+            if let Ok(value) = HeaderValue::from_str(&value) {
+                headers.insert(HeaderName::from_static(name), value);
+            }
+        }
+        headers
+    }
+}
+
+struct Buckets {
+    keys: HashMap<String, Bucket>,
+    swept_at: Instant,
+}
+
+pub struct RateLimiter {
+    limits: HashMap<String, RateLimit>,
+    buckets: Mutex<Buckets>,
+}
+
+impl RateLimiter {
+    pub fn new(limits: Vec<(String, RateLimit)>) -> Self {
+        Self {
+            limits: limits.into_iter().collect(),
+            buckets: Mutex::new(Buckets {
+                keys: HashMap::new(),
+                swept_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn from_config() -> Self {
+        Self::new(RATE_LIMITS.clone())
+    }
+
+    // routes with their own limit get their own bucket, the rest share the "*" bucket
+    fn policy<'a>(&self, route: &'a str) -> Option<(&'a str, RateLimit)> {
+        match self.limits.get(route) {
+            Some(policy) => Some((route, *policy)),
+            None => self.limits.get("*").map(|policy| ("*", *policy)),
+        }
+        .filter(|(_, policy)| policy.limit > 0)
+    }
+
+    pub fn take(&self, route: &str, subject: &str, now: Instant) -> Option<RateLimitStatus> {
+        let (route, policy) = self.policy(route)?;
+        let mut buckets = self.buckets.lock().unwrap();
+
+        if now.saturating_duration_since(buckets.swept_at) >= Duration::from_secs(SWEEP_SECONDS) {
+            buckets.keys.retain(|_, bucket| {
+                bucket.refill(now);
+                bucket.tokens < bucket.capacity
+            });
+            buckets.swept_at = now;
+        }
+
+        let bucket = buckets
+            .keys
+            .entry(format!("{}:{}", route, subject))
+            .or_insert_with(|| Bucket::new(policy, now));
+        bucket.refill(now);
+
+        let retry_after = match bucket.tokens >= 1.0 {
+            true => {
+                bucket.tokens -= 1.0;
+                None
+            }
+            false => Some(bucket.seconds_until(1.0).max(1)),
+        };
+
+        Some(RateLimitStatus {
+            policy,
+            remaining: bucket.tokens.floor() as u32,
+            reset: bucket.seconds_until(bucket.capacity),
+            retry_after,
+        })
+    }
+
+    pub fn refund(&self, route: &str, subject: &str, now: Instant) {
+        let Some((route, _)) = self.policy(route) else {
+            return;
+        };
+        let mut buckets = self.buckets.lock().unwrap();
+        if let Some(bucket) = buckets.keys.get_mut(&format!("{}:{}", route, subject)) {
+            bucket.refill(now);
+            bucket.tokens = (bucket.tokens + 1.0).min(bucket.capacity);
+        }
+    }
+}
+
+// set on the response by the account limiter so the address is not charged for the same request
+#[derive(Clone)]
+struct AccountCharged;
+
+fn matched_route(request: &Request) -> String {
+    request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+        .unwrap_or_else(|| request.uri().path().to_string())
+}
+
+fn with_headers(status: &RateLimitStatus, mut response: Response) -> Response {
+    response.headers_mut().extend(status.headers());
+    response
+}
+
+fn too_many_requests(status: &RateLimitStatus, retry_after: u64) -> Response {
+    with_headers(
+        status,
+        AppError::TooManyRequests("rate limit exceeded".to_string(), retry_after).into_response(),
+    )
+}
+
+// runs outside the auth middleware so requests with missing or forged credentials are counted per address,
+// signed in requests are handed back to the address and counted per account by account_rate_limit
+pub async fn rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let route = matched_route(&request);
+    let client = request
+        .extract_parts::<ClientInfoDto>()
+        .await
+        .unwrap_or_default();
+    let subject = match require_ip_address(client) {
+        Ok(ip_address) => format!("ip:{}", ip_address),
+        Err(e) => return e.into_response(),
+    };
+
+    let Some(status) = limiter.take(&route, &subject, Instant::now()) else {
+        return next.run(request).await;
+    };
+    if let Some(retry_after) = status.retry_after {
+        return too_many_requests(&status, retry_after);
+    }
+
+    let response = next.run(request).await;
+    match response.extensions().get::<AccountCharged>() {
+        Some(_) => {
+            limiter.refund(&route, &subject, Instant::now());
+            response
+        }
+        None => with_headers(&status, response),
+    }
+}
+
+// runs inside the auth middleware, requests without a member are left to rate_limit
+pub async fn account_rate_limit(
+    State(limiter): State<Arc<RateLimiter>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(auth_member) = request.extensions().get::<AuthMemberDto>() else {
+        return next.run(request).await;
+    };
+    let route = matched_route(&request);
+    let subject = format!("account:{}", auth_member.account);
+
+    let Some(status) = limiter.take(&route, &subject, Instant::now()) else {
+        return next.run(request).await;
+    };
+
+    let mut response = match status.retry_after {
+        Some(retry_after) => too_many_requests(&status, retry_after),
+        None => with_headers(&status, next.run(request).await),
+    };
+    response.extensions_mut().insert(AccountCharged);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons::setup;
+    use crate::middlewares::auth::auth_middleware;
+    use crate::use_cases::Modules;
+    use axum::{
+        Router,
+        body::Body,
+        extract::ConnectInfo,
+        http::{StatusCode, header},
+        middleware::from_fn_with_state,
+        routing::get,
+    };
+    use std::net::SocketAddr;
+    use tower::ServiceExt;
+
+    fn limiter() -> RateLimiter {
+        RateLimiter::new(vec![
+            ("*".to_string(), "3/60".parse().unwrap()),
+            (
+                "/service/contents/post".to_string(),
+                "1/10".parse().unwrap(),
+            ),
+            ("/service/auth/refresh".to_string(), "0/60".parse().unwrap()),
+        ])
+    }
+
+    #[test]
+    fn test_rate_limit_parse() {
+        assert_eq!(
+            "10/60".parse::<RateLimit>(),
+            Ok(RateLimit {
+                limit: 10,
+                window_seconds: 60
+            })
+        );
+        assert!("10".parse::<RateLimit>().is_err());
+        assert!("10/0".parse::<RateLimit>().is_err());
+        assert!("x/60".parse::<RateLimit>().is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = limiter();
+        let now = Instant::now();
+
+        for remaining in [2, 1, 0] {
+            let status = limiter.take("/service/contents", "account:a", now).unwrap();
+            assert_eq!(status.remaining, remaining);
+            assert!(status.retry_after.is_none());
+        }
+
+        // routes without their own limit share one bucket
+        let status = limiter
+            .take("/service/members/me", "account:a", now)
+            .unwrap();
+        assert_eq!(status.retry_after, Some(20));
+        assert_eq!(status.reset, 60);
+
+        let status = limiter.take("/service/contents", "account:b", now).unwrap();
+        assert!(status.retry_after.is_none());
+
+        let status = limiter
+            .take("/service/contents/post", "account:a", now)
+            .unwrap();
+        assert!(status.retry_after.is_none());
+        let status = limiter
+            .take("/service/contents/post", "account:a", now)
+            .unwrap();
+        assert_eq!(status.retry_after, Some(10));
+
+        // tokens refill over time
+        let now = now + Duration::from_secs(20);
+        let status = limiter.take("/service/contents", "account:a", now).unwrap();
+        assert!(status.retry_after.is_none());
+        assert_eq!(status.remaining, 0);
+
+        assert!(limiter.take("/service/auth/refresh", "ip:", now).is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_headers() {
+        let status = RateLimitStatus {
+            policy: "10/60".parse().unwrap(),
+            remaining: 9,
+            reset: 6,
+            retry_after: None,
+        };
+        let headers = status.headers();
+        assert_eq!(headers.get(RATE_LIMIT_LIMIT).unwrap(), "10");
+        assert_eq!(headers.get(RATE_LIMIT_REMAINING).unwrap(), "9");
+        assert_eq!(headers.get(RATE_LIMIT_RESET).unwrap(), "6");
+        assert_eq!(headers.get(RATE_LIMIT_POLICY).unwrap(), "10;w=60");
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_unauthorized() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+        let module = Arc::new(Modules::new(pool));
+        let limiter = Arc::new(RateLimiter::new(vec![(
+            "*".to_string(),
+            "2/60".parse().unwrap(),
+        )]));
+
+        // same layer order as create_handlers
+        let router = Router::new()
+            .route("/contents", get(|| async { "ok" }))
+            .route_layer(from_fn_with_state(limiter.clone(), account_rate_limit))
+            .route_layer(from_fn_with_state(module, auth_middleware))
+            .route_layer(from_fn_with_state(limiter, rate_limit));
+
+        let mut statuses = Vec::new();
+        for _ in 0..3 {
+            let mut request = Request::builder()
+                .uri("/contents")
+                .header(header::AUTHORIZATION, "Bearer forged")
+                .body(Body::empty())
+                .unwrap();
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 3000))));
+
+            let response = router.clone().oneshot(request).await.unwrap();
+            assert!(response.headers().contains_key(RATE_LIMIT_LIMIT));
+            assert!(response.headers().contains_key(RATE_LIMIT_REMAINING));
+            statuses.push(response.status());
+        }
+
+        assert_eq!(
+            statuses,
+            [
+                StatusCode::UNAUTHORIZED,
+                StatusCode::UNAUTHORIZED,
+                StatusCode::TOO_MANY_REQUESTS
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rate_limiter_refund() {
+        let limiter = limiter();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            limiter.take("/service/contents", "ip:a", now).unwrap();
+        }
+        limiter.refund("/service/contents", "ip:a", now);
+        let status = limiter.take("/service/contents", "ip:a", now).unwrap();
+        assert!(status.retry_after.is_none());
+        assert_eq!(status.remaining, 0);
+    }
+}
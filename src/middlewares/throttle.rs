@@ -0,0 +1,339 @@
+use crate::commons::config::{
+    LOGIN_THROTTLE_ACCOUNT_LIMIT, LOGIN_THROTTLE_DELAY_SECONDS, LOGIN_THROTTLE_IP_LIMIT,
+    LOGIN_THROTTLE_WINDOW_SECONDS,
+};
+use crate::commons::crypto::hash_token;
+use crate::commons::error::AppError;
+use crate::commons::validation::FieldError;
+use crate::middlewares::client::require_ip_address;
+use crate::models::dtos::session::ClientInfoDto;
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, Request, State},
+    http::StatusCode,
+    middleware::Next,
+    response::Response,
+};
+use serde::Deserialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const MAX_BODY_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleCount {
+    // only rejected requests count, a completed signin gives its attempt back
+    Failures,
+    Attempts,
+}
+
+struct Attempts {
+    keys: HashMap<String, VecDeque<Instant>>,
+    swept_at: Instant,
+}
+
+pub struct LoginThrottle {
+    window: Duration,
+    ip_limit: usize,
+    account_limit: usize,
+    delay: Duration,
+    count: ThrottleCount,
+    attempts: Mutex<Attempts>,
+}
+
+impl LoginThrottle {
+    pub fn new(
+        window: Duration,
+        ip_limit: usize,
+        account_limit: usize,
+        delay: Duration,
+        count: ThrottleCount,
+    ) -> Self {
+        Self {
+            window,
+            ip_limit,
+            account_limit,
+            delay,
+            count,
+            attempts: Mutex::new(Attempts {
+                keys: HashMap::new(),
+                swept_at: Instant::now(),
+            }),
+        }
+    }
+
+    pub fn from_config(count: ThrottleCount) -> Self {
+        Self::new(
+            Duration::from_secs(*LOGIN_THROTTLE_WINDOW_SECONDS),
+            *LOGIN_THROTTLE_IP_LIMIT,
+            *LOGIN_THROTTLE_ACCOUNT_LIMIT,
+            Duration::from_secs(*LOGIN_THROTTLE_DELAY_SECONDS),
+            count,
+        )
+    }
+
+    // a limit of 0 disables that key
+    fn keys(&self, ip_address: &str, account: Option<&str>) -> Vec<(String, usize)> {
+        let mut keys = vec![(format!("ip:{}", ip_address), self.ip_limit)];
+        if let Some(account) = account {
+            keys.push((
+                format!("account:{}:{}", ip_address, account),
+                self.account_limit,
+            ));
+        }
+        keys.into_iter().filter(|(_, limit)| *limit > 0).collect()
+    }
+
+    // checks and records in one lock so parallel requests cannot slip past the limit
+    pub fn acquire(
+        &self,
+        ip_address: &str,
+        account: Option<&str>,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let keys = self.keys(ip_address, account);
+        let mut attempts = self.attempts.lock().unwrap();
+
+        if now.saturating_duration_since(attempts.swept_at) >= self.window {
+            let window = self.window;
+            attempts.keys.retain(|_, times| {
+                Self::prune(times, window, now);
+                !times.is_empty()
+            });
+            attempts.swept_at = now;
+        }
+
+        let wait = keys
+            .iter()
+            .filter_map(|(key, limit)| {
+                let times = attempts.keys.get_mut(key)?;
+                Self::prune(times, self.window, now);
+                self.wait(times, *limit, now)
+            })
+            .max();
+        if let Some(wait) = wait {
+            return Err(wait);
+        }
+
+        for (key, _) in keys {
+            attempts.keys.entry(key).or_default().push_back(now);
+        }
+        Ok(())
+    }
+
+    pub fn release(&self, ip_address: &str, account: Option<&str>, at: Instant) {
+        let keys = self.keys(ip_address, account);
+        let mut attempts = self.attempts.lock().unwrap();
+
+        for (key, _) in keys {
+            let Some(times) = attempts.keys.get_mut(&key) else {
+                continue;
+            };
+            if let Some(index) = times.iter().rposition(|time| *time == at) {
+                times.remove(index);
+            }
+        }
+    }
+
+    fn prune(times: &mut VecDeque<Instant>, window: Duration, now: Instant) {
+        while times
+            .front()
+            .is_some_and(|time| now.saturating_duration_since(*time) >= window)
+        {
+            times.pop_front();
+        }
+    }
+
+    // the delay doubles with every attempt over the limit, up to the window itself
+    fn wait(&self, times: &VecDeque<Instant>, limit: usize, now: Instant) -> Option<Duration> {
+        if times.len() < limit {
+            return None;
+        }
+        let excess = (times.len() - limit).min(16) as u32;
+        let delay = self.delay.saturating_mul(1 << excess).min(self.window);
+        let elapsed = now.saturating_duration_since(*times.back()?);
+        (elapsed < delay).then(|| delay - elapsed)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountBody {
+    account: Option<String>,
+    challenge_token: Option<String>,
+}
+
+impl AccountBody {
+    // the second factor only carries the challenge, which stands for the account it was issued to
+    fn subject(self) -> Option<String> {
+        self.account.or_else(|| {
+            self.challenge_token
+                .map(|token| format!("challenge:{}", hash_token(&token)))
+        })
+    }
+}
+
+pub async fn login_throttle(
+    State(throttle): State<Arc<LoginThrottle>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let (mut parts, body) = request.into_parts();
+    let client = ClientInfoDto::from_request_parts(&mut parts, &()).await?;
+    let ip_address = require_ip_address(client)?;
+
+    let bytes = axum::body::to_bytes(body, MAX_BODY_SIZE)
+        .await
+        .map_err(|_| {
+            AppError::Validation(vec![FieldError::new("body", "request body is too large")])
+        })?;
+    let account = serde_json::from_slice::<AccountBody>(&bytes)
+        .ok()
+        .and_then(AccountBody::subject);
+
+    let now = Instant::now();
+    throttle
+        .acquire(&ip_address, account.as_deref(), now)
+        .map_err(|wait| {
+            AppError::TooManyRequests(
+                "too many attempts".to_string(),
+                wait.as_secs_f64().ceil() as u64,
+            )
+        })?;
+
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+
+    // 202 only asks for the second factor, the attempt stays until tokens are issued
+    if throttle.count == ThrottleCount::Failures && response.status() == StatusCode::OK {
+        throttle.release(&ip_address, account.as_deref(), now);
+    }
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router, extract::ConnectInfo, http::header, middleware::from_fn_with_state, routing::post,
+    };
+    use std::net::SocketAddr;
+    use tower::ServiceExt;
+
+    fn throttle(count: ThrottleCount) -> LoginThrottle {
+        LoginThrottle::new(Duration::from_secs(60), 4, 2, Duration::from_secs(1), count)
+    }
+
+    #[test]
+    fn test_login_throttle_account() {
+        let throttle = throttle(ThrottleCount::Failures);
+        let now = Instant::now();
+
+        assert!(throttle.acquire("10.0.0.1", Some("tester"), now).is_ok());
+        assert!(throttle.acquire("10.0.0.1", Some("tester"), now).is_ok());
+        assert_eq!(
+            throttle.acquire("10.0.0.1", Some("tester"), now),
+            Err(Duration::from_secs(1))
+        );
+
+        // other accounts and other addresses are not affected
+        assert!(throttle.acquire("10.0.0.1", Some("other"), now).is_ok());
+        assert!(throttle.acquire("10.0.0.2", Some("tester"), now).is_ok());
+
+        let now = now + Duration::from_secs(1);
+        assert!(throttle.acquire("10.0.0.1", Some("tester"), now).is_ok());
+
+        // progressive delay
+        assert_eq!(
+            throttle.acquire("10.0.0.1", Some("tester"), now),
+            Err(Duration::from_secs(2))
+        );
+
+        // sliding window
+        let now = now + Duration::from_secs(60);
+        assert!(throttle.acquire("10.0.0.1", Some("tester"), now).is_ok());
+    }
+
+    #[test]
+    fn test_login_throttle_ip() {
+        let throttle = throttle(ThrottleCount::Failures);
+        let now = Instant::now();
+
+        for account in ["a", "b", "c", "d"] {
+            assert!(throttle.acquire("10.0.0.1", Some(account), now).is_ok());
+        }
+        assert!(throttle.acquire("10.0.0.1", Some("e"), now).is_err());
+        assert!(throttle.acquire("10.0.0.1", None, now).is_err());
+        assert!(throttle.acquire("10.0.0.2", Some("e"), now).is_ok());
+
+        throttle.release("10.0.0.1", Some("d"), now);
+        assert!(throttle.acquire("10.0.0.1", Some("e"), now).is_ok());
+    }
+
+    #[test]
+    fn test_login_throttle_disabled() {
+        let throttle = LoginThrottle::new(
+            Duration::from_secs(60),
+            0,
+            0,
+            Duration::from_secs(1),
+            ThrottleCount::Attempts,
+        );
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            assert!(throttle.acquire("10.0.0.1", Some("tester"), now).is_ok());
+        }
+    }
+
+    async fn send(router: &Router, uri: &str, body: &str) -> StatusCode {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 3000))));
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_login_throttle_middleware() {
+        let throttle = Arc::new(throttle(ThrottleCount::Failures));
+        let router = Router::new()
+            .route("/signin", post(|| async { StatusCode::OK }))
+            .route("/challenge", post(|| async { StatusCode::ACCEPTED }))
+            .route(
+                "/second-factor",
+                post(|| async { StatusCode::UNAUTHORIZED }),
+            )
+            .layer(from_fn_with_state(throttle, login_throttle));
+
+        // completed signins give their attempt back
+        for _ in 0..3 {
+            let status = send(&router, "/signin", r#"{"account":"tester"}"#).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        // a second factor challenge is not a completed signin
+        for _ in 0..2 {
+            let status = send(&router, "/challenge", r#"{"account":"tester"}"#).await;
+            assert_eq!(status, StatusCode::ACCEPTED);
+        }
+        let status = send(&router, "/challenge", r#"{"account":"tester"}"#).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+
+        // codes are counted per challenge and against the same address
+        for _ in 0..2 {
+            let status = send(&router, "/second-factor", r#"{"challengeToken":"a"}"#).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+        let status = send(&router, "/second-factor", r#"{"challengeToken":"b"}"#).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    }
+}
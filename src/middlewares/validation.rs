@@ -0,0 +1,108 @@
+use crate::commons::error::AppError;
+use crate::commons::validation::{FieldError, Validate};
+use axum::{
+    Json,
+    extract::{FromRequest, Request, rejection::JsonRejection},
+};
+use serde::de::DeserializeOwned;
+
+pub struct ValidatedJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ValidatedJson<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(request, state)
+            .await
+            .map_err(|rejection| match rejection {
+                // only a well formed body with the wrong fields or types is a validation error
+                JsonRejection::JsonDataError(_) => {
+                    AppError::Validation(vec![FieldError::new("body", &rejection.body_text())])
+                }
+                rejection => AppError::Rejected(rejection.status(), rejection.body_text()),
+            })?;
+
+        value.validate()?;
+
+        Ok(Self(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dtos::auth::SigninDto;
+    use axum::{
+        body::Body,
+        http::{StatusCode, header},
+    };
+
+    fn request(body: &str) -> Request {
+        request_with(body, "application/json")
+    }
+
+    fn request_with(body: &str, content_type: &str) -> Request {
+        Request::builder()
+            .method("POST")
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_validated_json() {
+        let result = ValidatedJson::<SigninDto>::from_request(
+            request(r#"{"account": "tester", "password": "p@55w0rd"}"#),
+            &(),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        let result = ValidatedJson::<SigninDto>::from_request(
+            request(r#"{"account": "", "password": "p@55w0rd"}"#),
+            &(),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(AppError::Validation(errors)) if errors[0].field == "account")
+        );
+
+        let result =
+            ValidatedJson::<SigninDto>::from_request(request(r#"{"account": "tester"}"#), &())
+                .await;
+        assert!(matches!(result, Err(AppError::Validation(errors)) if errors[0].field == "body"));
+    }
+
+    #[tokio::test]
+    async fn test_validated_json_rejection() {
+        let body = r#"{"account": "tester", "password": "p@55w0rd"}"#;
+
+        let result =
+            ValidatedJson::<SigninDto>::from_request(request_with(body, "text/plain"), &()).await;
+        assert!(
+            matches!(result, Err(AppError::Rejected(status, _)) if status == StatusCode::UNSUPPORTED_MEDIA_TYPE)
+        );
+
+        let result = ValidatedJson::<SigninDto>::from_request(
+            Request::builder()
+                .method("POST")
+                .body(Body::from(body))
+                .unwrap(),
+            &(),
+        )
+        .await;
+        assert!(
+            matches!(result, Err(AppError::Rejected(status, _)) if status == StatusCode::UNSUPPORTED_MEDIA_TYPE)
+        );
+
+        let result =
+            ValidatedJson::<SigninDto>::from_request(request(r#"{"account": "#), &()).await;
+        assert!(
+            matches!(result, Err(AppError::Rejected(status, _)) if status == StatusCode::BAD_REQUEST)
+        );
+    }
+}
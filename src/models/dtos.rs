@@ -1,3 +1,7 @@
+pub mod access_token;
 pub mod auth;
+pub mod auth_event;
 pub mod content;
 pub mod member;
+pub mod pagination;
+pub mod session;
@@ -0,0 +1,116 @@
+use crate::commons::error::AppError;
+use crate::commons::validation::{Validate, Validator};
+use crate::models::entities::access_token::{AccessTokenEntity, Scope};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const MAX_EXPIRES_IN_DAYS: i64 = 3650;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessTokenCreateDto {
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    #[serde(default)]
+    pub expires_in_days: Option<i64>,
+}
+
+impl AccessTokenCreateDto {
+    pub fn expired_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_in_days
+            .map(|days| Utc::now() + chrono::Duration::days(days))
+    }
+}
+
+impl Validate for AccessTokenCreateDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("name", &self.name, 1, 64)
+            .check(!self.scopes.is_empty(), "scopes", "must not be empty")
+            .check(
+                self.expires_in_days
+                    .is_none_or(|days| (1..=MAX_EXPIRES_IN_DAYS).contains(&days)),
+                "expiresInDays",
+                &format!("must be between 1 and {}", MAX_EXPIRES_IN_DAYS),
+            )
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessTokenDto {
+    pub token_id: String,
+    pub name: String,
+    pub scopes: Vec<Scope>,
+    pub expired_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+impl AccessTokenDto {
+    pub fn from_entity(entity: AccessTokenEntity) -> Self {
+        Self {
+            scopes: entity.scopes(),
+            token_id: entity.token_id,
+            name: entity.name,
+            expired_at: entity.expired_at,
+            last_used_at: entity.last_used_at,
+            created_at: entity.created_at,
+            token: None,
+        }
+    }
+
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_token_create_dto_validate() {
+        let dto: AccessTokenCreateDto = serde_json::from_str(
+            r#"{"name": "ci", "scopes": ["content:read", "content:write"], "expiresInDays": 30}"#,
+        )
+        .unwrap();
+        assert!(dto.validate().is_ok());
+        assert_eq!(dto.scopes, vec![Scope::ContentRead, Scope::ContentWrite]);
+        assert!(dto.expired_at().is_some());
+
+        let dto = AccessTokenCreateDto {
+            name: "".to_string(),
+            scopes: vec![],
+            expires_in_days: Some(0),
+        };
+        match dto.validate() {
+            Err(AppError::Validation(errors)) => {
+                let fields = errors.iter().map(|e| e.field.as_str()).collect::<Vec<_>>();
+                assert_eq!(fields, vec!["name", "scopes", "expiresInDays"]);
+            }
+            _ => panic!("expected validation error"),
+        }
+
+        let result =
+            serde_json::from_str::<AccessTokenCreateDto>(r#"{"name": "ci", "scopes": ["admin"]}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_access_token_dto() {
+        let entity =
+            AccessTokenEntity::new("token", "tester", "ci", "hash", &[Scope::ContentRead], None);
+        let dto = AccessTokenDto::from_entity(entity);
+        assert_eq!(dto.scopes, vec![Scope::ContentRead]);
+        assert!(dto.token.is_none());
+
+        let json = serde_json::to_value(dto.with_token("pat_token")).unwrap();
+        assert_eq!(json["token"], "pat_token");
+        assert_eq!(json["scopes"][0], "content:read");
+    }
+}
@@ -1,4 +1,10 @@
-use crate::models::entities::member::MemberEntity;
+use crate::commons::config::TOMBSTONE_ACCOUNT;
+use crate::commons::error::AppError;
+use crate::commons::totp;
+use crate::commons::validation::{Validate, Validator, is_account, is_email, is_strong_password};
+use crate::models::entities::auth::AuthEntity;
+use crate::models::entities::member::{MemberEntity, MemberStatus, Role};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -18,15 +24,448 @@ impl SignupDto {
             password: self.password.clone(),
             name: self.name.clone(),
             email: self.email.clone(),
+            email_verified_at: None,
+            status: MemberStatus::Pending,
+            role: Role::Member,
             created_at: None,
             updated_at: None,
         }
     }
 }
 
+// static segments under /members, an account with one of these names could not be addressed
+pub const RESERVED_ACCOUNTS: [&str; 3] = ["me", "pending", "auth-events"];
+
+impl Validate for SignupDto {
+    fn validate(&self) -> Result<(), AppError> {
+        let mut validator = Validator::new();
+        validator
+            .length("account", &self.account, 1, 32)
+            .check(
+                is_account(&self.account),
+                "account",
+                "must contain only letters, digits, '_', '-' or '.'",
+            )
+            .check(
+                self.account != *TOMBSTONE_ACCOUNT
+                    && !RESERVED_ACCOUNTS.contains(&self.account.as_str()),
+                "account",
+                "is reserved",
+            )
+            .length("password", &self.password, 8, 128)
+            .check(
+                is_strong_password(&self.password),
+                "password",
+                "must contain both letters and digits",
+            )
+            .check(
+                self.password == self.confirm_password,
+                "confirmPassword",
+                "password does not match",
+            );
+        if let Some(name) = &self.name {
+            validator.length("name", name, 0, 64);
+        }
+        if let Some(email) = &self.email {
+            validator.length("email", email, 0, 256).check(
+                is_email(email),
+                "email",
+                "must be a valid email address",
+            );
+        }
+        validator.finish()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct SigninDto {
     pub account: String,
     pub password: String,
 }
+
+impl Validate for SigninDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("account", &self.account, 1, 32)
+            .length("password", &self.password, 1, 128)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenDto {
+    pub token: String,
+    pub refresh_token: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum SigninResultDto {
+    Token(TokenDto),
+    SecondFactor(SecondFactorChallengeDto),
+}
+
+impl SigninResultDto {
+    pub fn into_token(self) -> Option<TokenDto> {
+        match self {
+            Self::Token(token) => Some(token),
+            Self::SecondFactor(_) => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SecondFactorChallengeDto {
+    pub challenge_token: String,
+    pub expired_at: DateTime<Utc>,
+}
+
+impl SecondFactorChallengeDto {
+    pub fn new(challenge_token: &str, seconds: i64) -> Self {
+        Self {
+            challenge_token: challenge_token.to_string(),
+            expired_at: Utc::now() + chrono::Duration::seconds(seconds),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SecondFactorDto {
+    pub challenge_token: String,
+    pub code: String,
+}
+
+impl Validate for SecondFactorDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("challengeToken", &self.challenge_token, 1, 128)
+            .length("code", &self.code, 1, 32)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpEnrollmentDto {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpCodeDto {
+    pub code: String,
+}
+
+impl Validate for TotpCodeDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .check(totp::is_code(&self.code), "code", "must be 6 digits")
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TotpDisableDto {
+    pub password: String,
+}
+
+impl Validate for TotpDisableDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("password", &self.password, 1, 128)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryCodesDto {
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshDto {
+    #[serde(default)]
+    pub refresh_token: String,
+}
+
+impl Validate for RefreshDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("refreshToken", &self.refresh_token, 0, 128)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordDto {
+    pub current_password: String,
+    pub password: String,
+    pub confirm_password: String,
+}
+
+impl Validate for PasswordDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("currentPassword", &self.current_password, 1, 128)
+            .length("password", &self.password, 8, 128)
+            .check(
+                is_strong_password(&self.password),
+                "password",
+                "must contain both letters and digits",
+            )
+            .check(
+                self.password != self.current_password,
+                "password",
+                "must differ from the current password",
+            )
+            .check(
+                self.password == self.confirm_password,
+                "confirmPassword",
+                "password does not match",
+            )
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordResetRequestDto {
+    pub account: String,
+}
+
+impl Validate for PasswordResetRequestDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("account", &self.account, 1, 32)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PasswordResetConfirmDto {
+    pub token: String,
+    pub password: String,
+    pub confirm_password: String,
+}
+
+impl Validate for PasswordResetConfirmDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("token", &self.token, 1, 128)
+            .length("password", &self.password, 8, 128)
+            .check(
+                is_strong_password(&self.password),
+                "password",
+                "must contain both letters and digits",
+            )
+            .check(
+                self.password == self.confirm_password,
+                "confirmPassword",
+                "password does not match",
+            )
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailVerificationRequestDto {
+    pub account: String,
+}
+
+impl Validate for EmailVerificationRequestDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("account", &self.account, 1, 32)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailVerificationConfirmDto {
+    pub token: String,
+}
+
+impl Validate for EmailVerificationConfirmDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("token", &self.token, 1, 128)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LockStatusDto {
+    pub account: String,
+    pub missmatch: i32,
+    pub challenge_at: Option<DateTime<Utc>>,
+    pub unlock_at: Option<DateTime<Utc>>,
+    pub locked: bool,
+}
+
+impl LockStatusDto {
+    pub fn from_entity(account: &str, auth: Option<AuthEntity>) -> Self {
+        match auth {
+            Some(auth) => Self {
+                account: auth.account.clone(),
+                missmatch: auth.missmatch,
+                challenge_at: auth.challenge_at,
+                unlock_at: auth.unlock_at(),
+                locked: auth.is_locked(),
+            },
+            None => Self {
+                account: account.to_string(),
+                missmatch: 0,
+                challenge_at: None,
+                unlock_at: None,
+                locked: false,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signup_dto_validate() {
+        let dto = SignupDto {
+            account: "tester".to_string(),
+            password: "p@55w0rd".to_string(),
+            confirm_password: "p@55w0rd".to_string(),
+            name: Some("no name".to_string()),
+            email: Some("tester@local".to_string()),
+        };
+        assert!(dto.validate().is_ok());
+
+        let dto = SignupDto {
+            account: "tes ter".to_string(),
+            password: "password".to_string(),
+            confirm_password: "p@55w0rd".to_string(),
+            name: Some("x".repeat(65)),
+            email: Some("tester".to_string()),
+        };
+        match dto.validate() {
+            Err(AppError::Validation(errors)) => {
+                let fields = errors.iter().map(|e| e.field.as_str()).collect::<Vec<_>>();
+                assert_eq!(
+                    fields,
+                    vec!["account", "password", "confirmPassword", "name", "email"]
+                );
+            }
+            _ => panic!("expected validation error"),
+        }
+
+        let dto = SignupDto {
+            account: TOMBSTONE_ACCOUNT.clone(),
+            password: "p@55w0rd".to_string(),
+            confirm_password: "p@55w0rd".to_string(),
+            name: None,
+            email: None,
+        };
+        assert!(matches!(dto.validate(), Err(AppError::Validation(errors)) if errors.len() == 1));
+
+        for account in RESERVED_ACCOUNTS {
+            let dto = SignupDto {
+                account: account.to_string(),
+                password: "p@55w0rd".to_string(),
+                confirm_password: "p@55w0rd".to_string(),
+                name: None,
+                email: None,
+            };
+            assert!(
+                matches!(dto.validate(), Err(AppError::Validation(errors)) if errors[0].message == "is reserved")
+            );
+        }
+    }
+
+    #[test]
+    fn test_signin_dto_validate() {
+        let dto = SigninDto {
+            account: "tester".to_string(),
+            password: "p@55w0rd".to_string(),
+        };
+        assert!(dto.validate().is_ok());
+
+        let dto = SigninDto {
+            account: "".to_string(),
+            password: "".to_string(),
+        };
+        assert!(matches!(dto.validate(), Err(AppError::Validation(errors)) if errors.len() == 2));
+    }
+
+    #[test]
+    fn test_password_dto_validate() {
+        let dto = PasswordDto {
+            current_password: "p@55w0rd".to_string(),
+            password: "n3w-p@55w0rd".to_string(),
+            confirm_password: "n3w-p@55w0rd".to_string(),
+        };
+        assert!(dto.validate().is_ok());
+
+        let dto = PasswordDto {
+            current_password: "p@55w0rd".to_string(),
+            password: "p@55w0rd".to_string(),
+            confirm_password: "password".to_string(),
+        };
+        match dto.validate() {
+            Err(AppError::Validation(errors)) => {
+                let fields = errors.iter().map(|e| e.field.as_str()).collect::<Vec<_>>();
+                assert_eq!(fields, vec!["password", "confirmPassword"]);
+            }
+            _ => panic!("expected validation error"),
+        }
+    }
+
+    #[test]
+    fn test_second_factor_dto_validate() {
+        let dto = SecondFactorDto {
+            challenge_token: "challenge".to_string(),
+            code: "123456".to_string(),
+        };
+        assert!(dto.validate().is_ok());
+
+        let dto = SecondFactorDto {
+            challenge_token: "".to_string(),
+            code: "".to_string(),
+        };
+        assert!(matches!(dto.validate(), Err(AppError::Validation(errors)) if errors.len() == 2));
+
+        assert!(
+            TotpCodeDto {
+                code: "123456".to_string()
+            }
+            .validate()
+            .is_ok()
+        );
+        assert!(
+            TotpCodeDto {
+                code: "12345a".to_string()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_signin_result_dto_serialize() {
+        let result = SigninResultDto::SecondFactor(SecondFactorChallengeDto::new("challenge", 60));
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["challengeToken"], "challenge");
+        assert!(json.get("token").is_none());
+        assert!(result.into_token().is_none());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::models::dtos::pagination;
+use crate::models::entities::auth_event::{AuthEventEntity, AuthEventFilter, AuthEventType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthEventDto {
+    pub event_id: i64,
+    pub account: String,
+    pub event_type: AuthEventType,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl AuthEventDto {
+    pub fn from_entity(event: AuthEventEntity) -> Self {
+        Self {
+            event_id: event.event_id,
+            account: event.account,
+            event_type: event.event_type,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            created_at: event.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthEventQueryDto {
+    #[serde(default = "AuthEventQueryDto::default_page")]
+    pub page: i32,
+    #[serde(default = "AuthEventQueryDto::default_size")]
+    pub size: i32,
+    #[serde(default)]
+    pub account: Option<String>,
+    #[serde(default)]
+    pub event_type: Option<AuthEventType>,
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl Default for AuthEventQueryDto {
+    fn default() -> Self {
+        Self {
+            page: Self::default_page(),
+            size: Self::default_size(),
+            account: None,
+            event_type: None,
+            ip_address: None,
+            from: None,
+            to: None,
+        }
+    }
+}
+
+impl AuthEventQueryDto {
+    fn default_page() -> i32 {
+        1
+    }
+
+    fn default_size() -> i32 {
+        20
+    }
+
+    pub fn normalize(&self) -> Self {
+        Self {
+            page: pagination::clamp_page(self.page),
+            size: pagination::clamp_size(self.size),
+            account: self.account.clone().filter(|account| !account.is_empty()),
+            ip_address: self.ip_address.clone().filter(|ip| !ip.is_empty()),
+            ..self.clone()
+        }
+    }
+
+    pub fn to_filter(&self) -> AuthEventFilter {
+        AuthEventFilter {
+            account: self.account.clone(),
+            event_type: self.event_type,
+            ip_address: self.ip_address.clone(),
+            from: self.from,
+            to: self.to,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthEventPageDto {
+    pub items: Vec<AuthEventDto>,
+    pub page: i32,
+    pub size: i32,
+    pub total_count: i64,
+    pub total_pages: i64,
+}
+
+impl AuthEventPageDto {
+    pub fn new(items: Vec<AuthEventDto>, query: &AuthEventQueryDto, total_count: i64) -> Self {
+        Self {
+            items,
+            page: query.page,
+            size: query.size,
+            total_count,
+            total_pages: pagination::total_pages(total_count, query.size),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::dtos::pagination::{MAX_PAGE, MAX_PAGE_SIZE};
+
+    #[test]
+    fn test_auth_event_query_dto() {
+        let query: AuthEventQueryDto =
+            serde_urlencoded::from_str("eventType=signin_failed&size=1000&account=").unwrap();
+        let query = query.normalize();
+        assert_eq!(query.page, 1);
+        assert_eq!(query.size, MAX_PAGE_SIZE);
+        assert_eq!(query.event_type, Some(AuthEventType::SigninFailed));
+        assert!(query.account.is_none());
+
+        let filter = query.to_filter();
+        assert_eq!(filter.event_type, Some(AuthEventType::SigninFailed));
+
+        let page = AuthEventPageDto::new(vec![], &query, 101);
+        assert_eq!(page.total_pages, 2);
+
+        let query: AuthEventQueryDto =
+            serde_urlencoded::from_str("page=2147483647&size=100").unwrap();
+        assert_eq!(query.normalize().page, MAX_PAGE);
+    }
+}
@@ -1,3 +1,6 @@
+use crate::commons::error::AppError;
+use crate::commons::validation::{Validate, Validator};
+use crate::models::dtos::pagination;
 use crate::models::entities::content::ContentEntity;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -6,6 +9,7 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct ContentDto {
     pub content_id: i64,
+    #[serde(default)]
     pub account: String,
     pub post_at: DateTime<Utc>,
     pub title: String,
@@ -36,6 +40,110 @@ impl ContentDto {
     }
 }
 
+impl Validate for ContentDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("title", &self.title, 1, MAX_TITLE_LENGTH)
+            .check(
+                self.title.trim().len() == self.title.len(),
+                "title",
+                "must not start or end with whitespace",
+            )
+            .length("body", &self.body, 1, MAX_BODY_LENGTH)
+            .finish()
+    }
+}
+
+pub const MAX_TITLE_LENGTH: usize = 256;
+
+pub const MAX_BODY_LENGTH: usize = 65536;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentQueryDto {
+    #[serde(default = "ContentQueryDto::default_page")]
+    pub page: i32,
+    #[serde(default = "ContentQueryDto::default_size")]
+    pub size: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+impl ContentQueryDto {
+    fn default_page() -> i32 {
+        1
+    }
+
+    fn default_size() -> i32 {
+        10
+    }
+
+    pub fn normalize(&self) -> Self {
+        Self {
+            page: pagination::clamp_page(self.page),
+            size: pagination::clamp_size(self.size),
+            title: self.title.clone().filter(|title| !title.is_empty()),
+        }
+    }
+
+    pub fn with_page(&self, page: i32) -> Self {
+        Self {
+            page,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentPageDto {
+    pub items: Vec<ContentDto>,
+    pub page: i32,
+    pub size: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub total_count: i64,
+    pub total_pages: i64,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
+impl ContentPageDto {
+    pub fn new(items: Vec<ContentDto>, query: ContentQueryDto, total_count: i64) -> Self {
+        Self {
+            items,
+            page: query.page,
+            size: query.size,
+            title: query.title,
+            total_count,
+            total_pages: pagination::total_pages(total_count, query.size),
+            next: None,
+            prev: None,
+        }
+    }
+
+    pub fn with_links(mut self, path: &str) -> Self {
+        let query = ContentQueryDto {
+            page: self.page,
+            size: self.size,
+            title: self.title.clone(),
+        };
+        let link = |page: i32| {
+            serde_urlencoded::to_string(query.with_page(page))
+                .map(|params| format!("{}?{}", path, params))
+                .ok()
+        };
+
+        if (self.page as i64) < self.total_pages {
+            self.next = link(self.page + 1);
+        }
+        if self.page > 1 {
+            self.prev = link((self.page - 1).min(self.total_pages.max(1) as i32));
+        }
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,4 +163,63 @@ mod tests {
         let entity = dto.to_entity();
         assert_eq!(entity, content);
     }
+
+    #[test]
+    fn test_content_dto_validate() {
+        let mut dto = ContentDto {
+            content_id: 0,
+            account: "".to_string(),
+            post_at: Utc::now(),
+            title: "title".to_string(),
+            body: "body".to_string(),
+        };
+        assert!(dto.validate().is_ok());
+
+        dto.title = "".to_string();
+        dto.body = "x".repeat(MAX_BODY_LENGTH + 1);
+        match dto.validate() {
+            Err(AppError::Validation(errors)) => {
+                assert_eq!(errors.len(), 2);
+                assert_eq!(errors[0].field, "title");
+                assert_eq!(errors[1].field, "body");
+            }
+            _ => panic!("expected validation error"),
+        }
+
+        dto.title = "x".repeat(MAX_TITLE_LENGTH + 1);
+        dto.body = "body".to_string();
+        assert!(dto.validate().is_err());
+
+        dto.title = " title".to_string();
+        assert!(dto.validate().is_err());
+    }
+
+    #[test]
+    fn test_content_page_dto_links() {
+        let query = ContentQueryDto {
+            page: 2,
+            size: 10,
+            title: Some("a b".to_string()),
+        };
+        let page = ContentPageDto::new(vec![], query, 25).with_links("/service/contents");
+        assert_eq!(page.total_pages, 3);
+        assert_eq!(
+            page.next,
+            Some("/service/contents?page=3&size=10&title=a+b".to_string())
+        );
+        assert_eq!(
+            page.prev,
+            Some("/service/contents?page=1&size=10&title=a+b".to_string())
+        );
+
+        let query = ContentQueryDto {
+            page: 1,
+            size: 10,
+            title: None,
+        };
+        let page = ContentPageDto::new(vec![], query, 0).with_links("/service/contents");
+        assert_eq!(page.total_pages, 0);
+        assert!(page.next.is_none());
+        assert!(page.prev.is_none());
+    }
 }
@@ -1,29 +1,244 @@
-use crate::models::entities::{auth::AuthEntity, member::MemberEntity};
+use crate::commons::error::AppError;
+use crate::commons::validation::{Validate, Validator, is_email};
+use crate::models::dtos::auth_event::AuthEventDto;
+use crate::models::dtos::content::ContentDto;
+use crate::models::entities::{
+    access_token::{AccessTokenEntity, Scope},
+    auth::AuthEntity,
+    auth_event::AuthEventEntity,
+    content::ContentEntity,
+    member::{MemberEntity, MemberStatus, Role},
+    session::SessionEntity,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct AuthMemberDto {
     pub account: String,
     pub name: Option<String>,
     pub email: Option<String>,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub role: Role,
     pub login_at: Option<DateTime<Utc>>,
     pub prev_login_at: Option<DateTime<Utc>>,
+    pub session_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scopes: Option<Vec<Scope>>,
 }
 
 impl AuthMemberDto {
-    pub fn from_entity(member: MemberEntity, auth: AuthEntity) -> Self {
+    pub fn from_entity(member: MemberEntity, auth: AuthEntity, session: SessionEntity) -> Self {
         Self {
             account: member.account,
             name: member.name,
             email: member.email,
+            email_verified_at: member.email_verified_at,
+            role: member.role,
             login_at: auth.login_at,
             prev_login_at: auth.prev_login_at,
+            session_id: session.session_id,
+            scopes: None,
+        }
+    }
+
+    pub fn from_access_token(member: MemberEntity, token: AccessTokenEntity) -> Self {
+        Self {
+            account: member.account,
+            name: member.name,
+            email: member.email,
+            email_verified_at: member.email_verified_at,
+            role: member.role,
+            login_at: None,
+            prev_login_at: None,
+            session_id: String::new(),
+            scopes: Some(token.scopes()),
+        }
+    }
+
+    pub fn is_session(&self) -> bool {
+        self.scopes.is_none()
+    }
+
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes
+            .as_ref()
+            .is_none_or(|scopes| scopes.contains(&scope))
+    }
+
+    pub fn has_role(&self, role: Role) -> bool {
+        self.role.includes(role)
+    }
+
+    pub fn can_edit(&self, owner: &str) -> bool {
+        self.account == owner || self.has_role(Role::Editor)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberDto {
+    pub account: String,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub status: MemberStatus,
+    pub role: Role,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl MemberDto {
+    pub fn from_entity(member: MemberEntity) -> Self {
+        Self {
+            account: member.account,
+            name: member.name,
+            email: member.email,
+            email_verified_at: member.email_verified_at,
+            status: member.status,
+            role: member.role,
+            created_at: member.created_at,
+            updated_at: member.updated_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicMemberDto {
+    pub account: String,
+    pub name: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl PublicMemberDto {
+    pub fn from_entity(member: MemberEntity) -> Self {
+        Self {
+            account: member.account,
+            name: member.name,
+            created_at: member.created_at,
         }
     }
 }
 
+// omitted fields are left unchanged, an empty string clears the field
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileDto {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+impl ProfileDto {
+    pub fn apply(&self, member: &mut MemberEntity) -> bool {
+        if let Some(name) = &self.name {
+            member.name = Some(name.clone()).filter(|name| !name.is_empty());
+        }
+        match &self.email {
+            Some(email) => {
+                member.change_email(Some(email.clone()).filter(|email| !email.is_empty()))
+            }
+            None => false,
+        }
+    }
+}
+
+impl Validate for ProfileDto {
+    fn validate(&self) -> Result<(), AppError> {
+        let mut validator = Validator::new();
+        if let Some(name) = &self.name {
+            validator.length("name", name, 0, 64);
+        }
+        if let Some(email) = self.email.as_ref().filter(|email| !email.is_empty()) {
+            validator.length("email", email, 0, 256).check(
+                is_email(email),
+                "email",
+                "must be a valid email address",
+            );
+        }
+        validator.finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginHistoryDto {
+    pub login_at: Option<DateTime<Utc>>,
+    pub prev_login_at: Option<DateTime<Utc>>,
+    pub missmatch: i32,
+    pub challenge_at: Option<DateTime<Utc>>,
+}
+
+impl LoginHistoryDto {
+    pub fn from_entity(auth: AuthEntity) -> Self {
+        Self {
+            login_at: auth.login_at,
+            prev_login_at: auth.prev_login_at,
+            missmatch: auth.missmatch,
+            challenge_at: auth.challenge_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberExportDto {
+    pub exported_at: DateTime<Utc>,
+    pub member: MemberDto,
+    pub login: Option<LoginHistoryDto>,
+    pub auth_events: Vec<AuthEventDto>,
+    pub contents: Vec<ContentDto>,
+}
+
+impl MemberExportDto {
+    pub fn new(
+        member: MemberEntity,
+        auth: Option<AuthEntity>,
+        auth_events: Vec<AuthEventEntity>,
+        contents: Vec<ContentEntity>,
+    ) -> Self {
+        Self {
+            exported_at: Utc::now(),
+            member: MemberDto::from_entity(member),
+            login: auth.map(LoginHistoryDto::from_entity),
+            auth_events: auth_events
+                .into_iter()
+                .map(AuthEventDto::from_entity)
+                .collect(),
+            contents: contents.into_iter().map(ContentDto::from_entity).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDeletionDto {
+    pub password: String,
+}
+
+impl Validate for AccountDeletionDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Validator::new()
+            .length("password", &self.password, 1, 128)
+            .finish()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RoleDto {
+    pub role: Role,
+}
+
+impl Validate for RoleDto {
+    fn validate(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OptionAuthMemberDto {
@@ -35,6 +250,7 @@ mod tests {
     use super::*;
     use crate::models::entities::auth::AuthEntity;
     use crate::models::entities::member::MemberEntity;
+    use crate::models::entities::session::SessionEntity;
     use chrono::Utc;
 
     #[test]
@@ -44,6 +260,9 @@ mod tests {
             password: "test".to_string(),
             name: Some("test".to_string()),
             email: Some("test".to_string()),
+            email_verified_at: None,
+            status: MemberStatus::Active,
+            role: Role::Member,
             created_at: None,
             updated_at: None,
         };
@@ -57,11 +276,84 @@ mod tests {
             login_at: Some(Utc::now()),
             prev_login_at: None,
         };
-        let dto = AuthMemberDto::from_entity(member.clone(), auth.clone());
+        let claims = crate::commons::jwt::Claims::new("test", 60);
+        let session = SessionEntity::new("session", claims, None, None);
+        let dto = AuthMemberDto::from_entity(member.clone(), auth.clone(), session);
         assert_eq!(dto.account, member.account);
         assert_eq!(dto.name, member.name);
         assert_eq!(dto.email, member.email);
+        assert_eq!(dto.role, member.role);
         assert_eq!(dto.login_at, auth.login_at);
         assert_eq!(dto.prev_login_at, auth.prev_login_at);
+        assert_eq!(dto.session_id, "session".to_string());
+    }
+
+    #[test]
+    fn test_auth_member_dto_can_edit() {
+        let mut dto = AuthMemberDto {
+            account: "test".to_string(),
+            ..Default::default()
+        };
+        assert!(dto.can_edit("test"));
+        assert!(!dto.can_edit("other"));
+        assert!(!dto.has_role(Role::Editor));
+
+        dto.role = Role::Editor;
+        assert!(dto.can_edit("other"));
+        assert!(!dto.has_role(Role::Admin));
+    }
+
+    #[test]
+    fn test_auth_member_dto_has_scope() {
+        let mut dto = AuthMemberDto {
+            account: "test".to_string(),
+            ..Default::default()
+        };
+        assert!(dto.is_session());
+        assert!(dto.has_scope(Scope::ContentWrite));
+
+        dto.scopes = Some(vec![Scope::ContentRead]);
+        assert!(!dto.is_session());
+        assert!(dto.has_scope(Scope::ContentRead));
+        assert!(!dto.has_scope(Scope::ContentWrite));
+    }
+
+    #[test]
+    fn test_profile_dto() {
+        let mut member = MemberEntity {
+            account: "test".to_string(),
+            password: "test".to_string(),
+            name: Some("test".to_string()),
+            email: Some("test@local".to_string()),
+            email_verified_at: Some(Utc::now()),
+            status: MemberStatus::Active,
+            role: Role::Member,
+            created_at: None,
+            updated_at: None,
+        };
+
+        let dto = ProfileDto {
+            name: Some("renamed".to_string()),
+            email: None,
+        };
+        assert!(dto.validate().is_ok());
+        assert!(!dto.apply(&mut member));
+        assert_eq!(member.name, Some("renamed".to_string()));
+        assert!(member.email_verified_at.is_some());
+
+        let dto = ProfileDto {
+            name: Some("".to_string()),
+            email: Some("changed@local".to_string()),
+        };
+        assert!(dto.apply(&mut member));
+        assert!(member.name.is_none());
+        assert_eq!(member.email, Some("changed@local".to_string()));
+        assert!(member.email_verified_at.is_none());
+
+        let dto = ProfileDto {
+            name: None,
+            email: Some("invalid".to_string()),
+        };
+        assert!(dto.validate().is_err());
     }
 }
@@ -0,0 +1,40 @@
+pub const MAX_PAGE_SIZE: i32 = 100;
+
+// keeps (page - 1) * size within i32 for every accepted size
+pub const MAX_PAGE: i32 = i32::MAX / MAX_PAGE_SIZE;
+
+pub fn clamp_page(page: i32) -> i32 {
+    page.clamp(1, MAX_PAGE)
+}
+
+pub fn clamp_size(size: i32) -> i32 {
+    size.clamp(1, MAX_PAGE_SIZE)
+}
+
+pub fn offset(page: i32, size: i32) -> i64 {
+    (page as i64 - 1).max(0) * size as i64
+}
+
+pub fn total_pages(total_count: i64, size: i32) -> i64 {
+    let size = size.max(1) as i64;
+    (total_count + size - 1) / size
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pagination() {
+        assert_eq!(clamp_page(0), 1);
+        assert_eq!(clamp_page(i32::MAX), MAX_PAGE);
+        assert_eq!(clamp_size(1000), MAX_PAGE_SIZE);
+
+        assert_eq!(offset(1, 10), 0);
+        assert_eq!(offset(3, 10), 20);
+        assert!(offset(i32::MAX, i32::MAX) > 0);
+
+        assert_eq!(total_pages(0, 10), 0);
+        assert_eq!(total_pages(25, 10), 3);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::models::entities::session::SessionEntity;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientInfoDto {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDto {
+    pub session_id: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub issued_at: Option<DateTime<Utc>>,
+    pub expired_at: Option<DateTime<Utc>>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub current: bool,
+}
+
+impl SessionDto {
+    pub fn from_entity(session: SessionEntity, current_session_id: &str) -> Self {
+        Self {
+            current: session.session_id == current_session_id,
+            session_id: session.session_id,
+            user_agent: session.user_agent,
+            ip_address: session.ip_address,
+            issued_at: DateTime::from_timestamp(session.issued_tm, 0),
+            expired_at: DateTime::from_timestamp(session.expired_tm, 0),
+            last_seen_at: session.last_seen_at,
+            created_at: session.created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_dto() {
+        let claims = crate::commons::jwt::Claims::new("tester", 60);
+        let session = SessionEntity::new("session", claims.clone(), None, None);
+
+        let dto = SessionDto::from_entity(session.clone(), "session");
+        assert!(dto.current);
+        assert_eq!(dto.issued_at.unwrap().timestamp(), claims.iat);
+        assert_eq!(dto.expired_at.unwrap().timestamp(), claims.exp);
+
+        let dto = SessionDto::from_entity(session, "other");
+        assert!(!dto.current);
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+pub const ACCESS_TOKEN_PREFIX: &str = "pat_";
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    #[serde(rename = "content:read")]
+    ContentRead,
+    #[serde(rename = "content:write")]
+    ContentWrite,
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ContentRead => "content:read",
+            Self::ContentWrite => "content:write",
+        }
+    }
+}
+
+impl std::str::FromStr for Scope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "content:read" => Ok(Self::ContentRead),
+            "content:write" => Ok(Self::ContentWrite),
+            _ => Err(format!("invalid scope: {}", s)),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct AccessTokenEntity {
+    pub token_id: String,
+    pub account: String,
+    pub name: String,
+    pub token_hash: String,
+    pub scopes: String,
+    pub expired_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl AccessTokenEntity {
+    pub fn new(
+        token_id: &str,
+        account: &str,
+        name: &str,
+        token_hash: &str,
+        scopes: &[Scope],
+        expired_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self {
+            token_id: token_id.to_string(),
+            account: account.to_string(),
+            name: name.to_string(),
+            token_hash: token_hash.to_string(),
+            scopes: scopes
+                .iter()
+                .map(|scope| scope.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            expired_at,
+            last_used_at: None,
+            created_at: None,
+        }
+    }
+
+    pub fn scopes(&self) -> Vec<Scope> {
+        self.scopes
+            .split_whitespace()
+            .filter_map(|scope| scope.parse().ok())
+            .collect()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.expired_at
+            .is_some_and(|expired_at| Utc::now() > expired_at)
+    }
+
+    pub fn is_stale(&self, seconds: i64) -> bool {
+        match self.last_used_at {
+            Some(last_used_at) => Utc::now() - last_used_at > chrono::Duration::seconds(seconds),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_token_scopes() {
+        let entity = AccessTokenEntity::new(
+            "id",
+            "tester",
+            "ci",
+            "hash",
+            &[Scope::ContentRead, Scope::ContentWrite],
+            None,
+        );
+        assert_eq!(entity.scopes, "content:read content:write".to_string());
+        assert_eq!(
+            entity.scopes(),
+            vec![Scope::ContentRead, Scope::ContentWrite]
+        );
+        assert!(!entity.is_expired());
+
+        let entity = AccessTokenEntity::new(
+            "id",
+            "tester",
+            "ci",
+            "hash",
+            &[Scope::ContentRead],
+            Some(Utc::now() - chrono::Duration::seconds(1)),
+        );
+        assert_eq!(entity.scopes(), vec![Scope::ContentRead]);
+        assert!(entity.is_expired());
+    }
+}
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct AuditLogEntity {
+    pub audit_id: i64,
+    pub actor: String,
+    pub action: String,
+    pub target: String,
+    pub detail: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl AuditLogEntity {
+    pub fn new(actor: &str, action: &str, target: &str, detail: Option<String>) -> Self {
+        Self {
+            audit_id: 0,
+            actor: actor.to_string(),
+            action: action.to_string(),
+            target: target.to_string(),
+            detail,
+            created_at: None,
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use crate::commons::config::{LOCK_HOUR, MAX_MISSMATCH_COUNT};
+use crate::commons::jwt::Claims;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use simple_jwt::Claims;
 use sqlx::FromRow;
 
 #[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
@@ -9,6 +9,7 @@ pub struct AuthEntity {
     pub account: String,
     pub issued_tm: Option<i64>,
     pub expired_tm: Option<i64>,
+    // the jti lives in session now, the column is only kept so older databases still load
     pub jwt_id: Option<String>,
     pub missmatch: i32,
     pub challenge_at: Option<DateTime<Utc>>,
@@ -41,7 +42,7 @@ impl AuthEntity {
             account: claims.sub,
             issued_tm: Some(claims.iat),
             expired_tm: Some(claims.exp),
-            jwt_id: Some(claims.jti),
+            jwt_id: None,
             missmatch: 0,
             challenge_at: None,
             login_at: Some(Utc::now()),
@@ -50,46 +51,37 @@ impl AuthEntity {
     }
 
     pub fn signin(&mut self, claims: Claims) {
-        self.jwt_id = Some(claims.jti.clone());
-        self.issued_tm = Some(claims.iat.clone());
-        self.expired_tm = Some(claims.exp.clone());
-        self.jwt_id = Some(claims.jti.clone());
+        self.jwt_id = None;
+        self.issued_tm = Some(claims.iat);
+        self.expired_tm = Some(claims.exp);
         self.missmatch = 0;
         self.challenge_at = None;
         self.prev_login_at = self.login_at;
         self.login_at = Some(Utc::now());
     }
 
-    pub fn is_timeout(&self) -> bool {
-        if let Some(expired_tm) = self.expired_tm {
-            Utc::now().timestamp() > expired_tm
-        } else {
-            true
+    pub fn unlock_at(&self) -> Option<DateTime<Utc>> {
+        if self.missmatch < *MAX_MISSMATCH_COUNT {
+            return None;
         }
+        self.challenge_at.map(|challenge_at| {
+            challenge_at + chrono::Duration::hours(*LOCK_HOUR * self.missmatch as i64)
+        })
     }
 
     pub fn is_locked(&self) -> bool {
         if self.missmatch < *MAX_MISSMATCH_COUNT {
             return false;
         }
-        if self.challenge_at.is_none() {
-            return true;
+        match self.unlock_at() {
+            Some(unlock_at) => Utc::now() < unlock_at,
+            None => true,
         }
-        let challenge_at = self.challenge_at.unwrap();
-        let unlock_at = challenge_at + chrono::Duration::hours(*LOCK_HOUR * self.missmatch as i64);
-
-        println!("{:?} < {:?}", Utc::now(), unlock_at);
-
-        Utc::now() < unlock_at
     }
 
-    pub fn is_signin(&self, claims: Claims) -> bool {
-        self.jwt_id == Some(claims.jti)
-            && self.issued_tm == Some(claims.iat)
-            && self.expired_tm == Some(claims.exp)
-            && self.missmatch == 0
-            && self.challenge_at.is_none()
-            && !self.is_timeout()
+    pub fn unlock(&mut self) {
+        self.missmatch = 0;
+        self.challenge_at = None;
     }
 
     pub fn signout(&mut self) {
@@ -118,9 +110,9 @@ mod tests {
 
     #[test]
     fn test_auth_signin() {
-        let claim = simple_jwt::Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
+        let claim = Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
         let mut auth = AuthEntity::new_signin(claim);
-        assert!(auth.jwt_id.is_some());
+        assert!(auth.jwt_id.is_none());
         assert!(auth.issued_tm.is_some());
         assert!(auth.expired_tm.is_some());
         assert_eq!(auth.missmatch, 0);
@@ -130,9 +122,9 @@ mod tests {
 
         auth.missmatched();
 
-        let claim = simple_jwt::Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
+        let claim = Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
         auth.signin(claim);
-        assert!(auth.jwt_id.is_some());
+        assert!(auth.jwt_id.is_none());
         assert!(auth.issued_tm.is_some());
         assert!(auth.expired_tm.is_some());
         assert_eq!(auth.missmatch, 0);
@@ -141,21 +133,11 @@ mod tests {
         assert!(auth.prev_login_at.is_some());
     }
 
-    #[test]
-    fn test_auth_is_timeout() {
-        let claim = simple_jwt::Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
-        let mut auth = AuthEntity::new_signin(claim);
-        assert!(!auth.is_timeout());
-
-        auth.expired_tm = Some(Utc::now().timestamp() - 1);
-        assert!(auth.is_timeout());
-    }
-
     #[test]
     fn test_auth_signout() {
-        let claim = simple_jwt::Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
+        let claim = Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
         let mut auth = AuthEntity::new_signin(claim);
-        assert!(auth.jwt_id.is_some());
+        assert!(auth.jwt_id.is_none());
         assert!(auth.issued_tm.is_some());
         assert!(auth.expired_tm.is_some());
         assert_eq!(auth.missmatch, 0);
@@ -171,7 +153,7 @@ mod tests {
 
     #[test]
     fn test_auth_is_locked() {
-        let claim = simple_jwt::Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
+        let claim = Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
         let mut auth = AuthEntity::new_signin(claim);
         assert!(!auth.is_locked());
 
@@ -188,7 +170,7 @@ mod tests {
 
     #[test]
     fn test_autn_is_not_locked() {
-        let claim = simple_jwt::Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
+        let claim = Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
         let mut auth = AuthEntity::new_signin(claim);
         assert!(!auth.is_locked());
 
@@ -204,16 +186,23 @@ mod tests {
     }
 
     #[test]
-    fn test_auth_is_signin() {
-        let claim = simple_jwt::Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
-        let mut auth = AuthEntity::new_signin(claim.clone());
-        assert!(auth.is_signin(claim));
+    fn test_auth_unlock() {
+        let mut auth = AuthEntity::new_missmatched("tester".to_string());
+        assert!(auth.unlock_at().is_none());
+
+        auth.missmatched();
+        auth.missmatched();
+        assert!(auth.is_locked());
 
-        let claim = simple_jwt::Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
-        auth.signin(claim.clone());
-        assert!(auth.is_signin(claim.clone()));
+        let challenge_at = auth.challenge_at.unwrap();
+        assert_eq!(
+            auth.unlock_at(),
+            Some(challenge_at + chrono::Duration::hours(*LOCK_HOUR * 3))
+        );
 
-        auth.signout();
-        assert!(!auth.is_signin(claim));
+        auth.unlock();
+        assert!(!auth.is_locked());
+        assert_eq!(auth.missmatch, 0);
+        assert!(auth.unlock_at().is_none());
     }
 }
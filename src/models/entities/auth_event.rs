@@ -0,0 +1,56 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Serialize, Deserialize, sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum AuthEventType {
+    Signin,
+    SigninFailed,
+    SigninLocked,
+    SecondFactorFailed,
+    ReauthFailed,
+    AccountLocked,
+    Signout,
+    SignoutAll,
+    TokenRejected,
+    RefreshRejected,
+}
+
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct AuthEventEntity {
+    pub event_id: i64,
+    pub account: String,
+    pub event_type: AuthEventType,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl AuthEventEntity {
+    pub fn new(
+        account: &str,
+        event_type: AuthEventType,
+        ip_address: Option<String>,
+        user_agent: Option<String>,
+    ) -> Self {
+        Self {
+            event_id: 0,
+            account: account.to_string(),
+            event_type,
+            ip_address,
+            user_agent,
+            created_at: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct AuthEventFilter {
+    pub account: Option<String>,
+    pub event_type: Option<AuthEventType>,
+    pub ip_address: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
@@ -1,13 +1,125 @@
+use crate::commons::config::RegistrationMode;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+#[derive(Serialize, Deserialize, sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum MemberStatus {
+    Pending,
+    #[default]
+    Active,
+    Suspended,
+}
+
+impl MemberStatus {
+    pub fn on_signup(mode: RegistrationMode, first_member: bool) -> Self {
+        match (mode, first_member) {
+            (RegistrationMode::Approval, false) => Self::Pending,
+            _ => Self::Active,
+        }
+    }
+}
+
+#[derive(
+    Serialize, Deserialize, sqlx::Type, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default,
+)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(rename_all = "lowercase")]
+pub enum Role {
+    #[default]
+    Member,
+    Editor,
+    Admin,
+}
+
+impl Role {
+    pub fn includes(&self, role: Role) -> bool {
+        *self >= role
+    }
+}
+
 #[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
 pub struct MemberEntity {
     pub account: String,
     pub password: String,
     pub name: Option<String>,
     pub email: Option<String>,
+    pub email_verified_at: Option<DateTime<Utc>>,
+    pub status: MemberStatus,
+    pub role: Role,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
 }
+
+impl MemberEntity {
+    pub fn is_active(&self) -> bool {
+        self.status == MemberStatus::Active
+    }
+
+    pub fn change_email(&mut self, email: Option<String>) -> bool {
+        if self.email == email {
+            return false;
+        }
+        self.email = email;
+        self.email_verified_at = None;
+        true
+    }
+
+    pub fn verify_email(&mut self) {
+        self.email_verified_at = Some(Utc::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_includes() {
+        assert!(Role::Admin.includes(Role::Editor));
+        assert!(Role::Editor.includes(Role::Editor));
+        assert!(!Role::Editor.includes(Role::Admin));
+        assert!(!Role::Member.includes(Role::Editor));
+    }
+
+    #[test]
+    fn test_member_change_email() {
+        let mut member = MemberEntity {
+            account: "tester".to_string(),
+            password: "password".to_string(),
+            name: None,
+            email: Some("tester@local".to_string()),
+            email_verified_at: None,
+            status: MemberStatus::Active,
+            role: Role::Member,
+            created_at: None,
+            updated_at: None,
+        };
+        member.verify_email();
+        assert!(member.email_verified_at.is_some());
+
+        assert!(!member.change_email(Some("tester@local".to_string())));
+        assert!(member.email_verified_at.is_some());
+
+        assert!(member.change_email(Some("other@local".to_string())));
+        assert!(member.email_verified_at.is_none());
+    }
+
+    #[test]
+    fn test_member_status_on_signup() {
+        assert_eq!(
+            MemberStatus::on_signup(RegistrationMode::Approval, true),
+            MemberStatus::Active
+        );
+        assert_eq!(
+            MemberStatus::on_signup(RegistrationMode::Approval, false),
+            MemberStatus::Pending
+        );
+        assert_eq!(
+            MemberStatus::on_signup(RegistrationMode::Open, false),
+            MemberStatus::Active
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Serialize, Deserialize, sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(rename_all = "snake_case")]
+pub enum TokenPurpose {
+    PasswordReset,
+    EmailVerification,
+    SecondFactor,
+}
+
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct OneTimeTokenEntity {
+    pub token_hash: String,
+    pub account: String,
+    pub purpose: TokenPurpose,
+    pub expired_at: DateTime<Utc>,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl OneTimeTokenEntity {
+    pub fn new(account: &str, purpose: TokenPurpose, token_hash: &str, seconds: i64) -> Self {
+        Self {
+            token_hash: token_hash.to_string(),
+            account: account.to_string(),
+            purpose,
+            expired_at: Utc::now() + chrono::Duration::seconds(seconds),
+            used_at: None,
+            created_at: None,
+        }
+    }
+
+    pub fn is_valid(&self, purpose: TokenPurpose) -> bool {
+        self.purpose == purpose && self.used_at.is_none() && Utc::now() <= self.expired_at
+    }
+
+    pub fn consume(&mut self) {
+        self.used_at = Some(Utc::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_one_time_token_is_valid() {
+        let mut entity = OneTimeTokenEntity::new("tester", TokenPurpose::PasswordReset, "hash", 60);
+        assert!(entity.is_valid(TokenPurpose::PasswordReset));
+
+        entity.consume();
+        assert!(!entity.is_valid(TokenPurpose::PasswordReset));
+
+        let entity = OneTimeTokenEntity::new("tester", TokenPurpose::PasswordReset, "hash", -1);
+        assert!(!entity.is_valid(TokenPurpose::PasswordReset));
+
+        let entity = OneTimeTokenEntity::new("tester", TokenPurpose::EmailVerification, "hash", 60);
+        assert!(!entity.is_valid(TokenPurpose::PasswordReset));
+    }
+}
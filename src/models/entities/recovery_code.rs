@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct RecoveryCodeEntity {
+    pub code_hash: String,
+    pub account: String,
+    pub used_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl RecoveryCodeEntity {
+    pub fn new(account: &str, code_hash: &str) -> Self {
+        Self {
+            code_hash: code_hash.to_string(),
+            account: account.to_string(),
+            used_at: None,
+            created_at: None,
+        }
+    }
+
+    pub fn is_valid(&self, account: &str) -> bool {
+        self.account == account && self.used_at.is_none()
+    }
+
+    pub fn consume(&mut self) {
+        self.used_at = Some(Utc::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recovery_code_is_valid() {
+        let mut entity = RecoveryCodeEntity::new("tester", "hash");
+        assert!(entity.is_valid("tester"));
+        assert!(!entity.is_valid("other"));
+
+        entity.consume();
+        assert!(!entity.is_valid("tester"));
+    }
+}
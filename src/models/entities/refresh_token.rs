@@ -0,0 +1,59 @@
+use crate::commons::config::REFRESH_TOKEN_EXPIRATION_SECONDS;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct RefreshTokenEntity {
+    pub token_hash: String,
+    pub family_id: String,
+    pub account: String,
+    pub expired_at: DateTime<Utc>,
+    pub rotated_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl RefreshTokenEntity {
+    pub fn new(account: &str, family_id: &str, token_hash: &str) -> Self {
+        Self {
+            token_hash: token_hash.to_string(),
+            family_id: family_id.to_string(),
+            account: account.to_string(),
+            expired_at: Utc::now() + chrono::Duration::seconds(*REFRESH_TOKEN_EXPIRATION_SECONDS),
+            rotated_at: None,
+            revoked_at: None,
+            created_at: None,
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expired_at
+    }
+
+    pub fn rotate(&mut self) {
+        self.rotated_at = Some(Utc::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_refresh_token_rotate() {
+        let mut entity = RefreshTokenEntity::new("tester", "family", "hash");
+        assert!(entity.rotated_at.is_none());
+        assert!(!entity.is_expired());
+
+        entity.rotate();
+        assert!(entity.rotated_at.is_some());
+    }
+
+    #[test]
+    fn test_refresh_token_is_expired() {
+        let mut entity = RefreshTokenEntity::new("tester", "family", "hash");
+        entity.expired_at = Utc::now() - chrono::Duration::seconds(1);
+        assert!(entity.is_expired());
+    }
+}
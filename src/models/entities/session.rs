@@ -0,0 +1,96 @@
+use crate::commons::jwt::Claims;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct SessionEntity {
+    pub session_id: String,
+    pub account: String,
+    pub jwt_id: String,
+    pub issued_tm: i64,
+    pub expired_tm: i64,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl SessionEntity {
+    pub fn new(
+        session_id: &str,
+        claims: Claims,
+        user_agent: Option<String>,
+        ip_address: Option<String>,
+    ) -> Self {
+        Self {
+            session_id: session_id.to_string(),
+            account: claims.sub,
+            jwt_id: claims.jti,
+            issued_tm: claims.iat,
+            expired_tm: claims.exp,
+            user_agent,
+            ip_address,
+            last_seen_at: Some(Utc::now()),
+            created_at: None,
+        }
+    }
+
+    pub fn refreshed(&mut self, claims: Claims) {
+        self.jwt_id = claims.jti;
+        self.issued_tm = claims.iat;
+        self.expired_tm = claims.exp;
+        self.last_seen_at = Some(Utc::now());
+    }
+
+    pub fn is_timeout(&self) -> bool {
+        Utc::now().timestamp() > self.expired_tm
+    }
+
+    pub fn is_signin(&self, claims: &Claims) -> bool {
+        self.account == claims.sub
+            && self.jwt_id == claims.jti
+            && self.issued_tm == claims.iat
+            && self.expired_tm == claims.exp
+            && !self.is_timeout()
+    }
+
+    pub fn is_stale(&self, seconds: i64) -> bool {
+        match self.last_seen_at {
+            Some(last_seen_at) => Utc::now() - last_seen_at > chrono::Duration::seconds(seconds),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons;
+
+    #[test]
+    fn test_session_is_signin() {
+        let claims = Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
+        let mut session = SessionEntity::new("session", claims.clone(), None, None);
+        assert!(session.is_signin(&claims));
+
+        let refreshed = Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
+        session.refreshed(refreshed.clone());
+        assert!(!session.is_signin(&claims));
+        assert!(session.is_signin(&refreshed));
+
+        session.expired_tm = Utc::now().timestamp() - 1;
+        assert!(session.is_timeout());
+        assert!(!session.is_signin(&refreshed));
+    }
+
+    #[test]
+    fn test_session_is_stale() {
+        let claims = Claims::new("tester", *commons::config::JWT_EXPIRATION_SECONDS);
+        let mut session = SessionEntity::new("session", claims, None, None);
+        assert!(!session.is_stale(60));
+
+        session.last_seen_at = Some(Utc::now() - chrono::Duration::seconds(61));
+        assert!(session.is_stale(60));
+    }
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Serialize, Deserialize, FromRow, Debug, Clone)]
+pub struct TotpEntity {
+    pub account: String,
+    pub secret: String,
+    pub last_step: i64,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl TotpEntity {
+    pub fn new(account: &str, secret: &str) -> Self {
+        Self {
+            account: account.to_string(),
+            secret: secret.to_string(),
+            last_step: 0,
+            confirmed_at: None,
+            created_at: None,
+        }
+    }
+
+    pub fn is_confirmed(&self) -> bool {
+        self.confirmed_at.is_some()
+    }
+
+    pub fn accept(&mut self, step: i64) {
+        self.last_step = step;
+        if self.confirmed_at.is_none() {
+            self.confirmed_at = Some(Utc::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totp_accept() {
+        let mut entity = TotpEntity::new("tester", "secret");
+        assert!(!entity.is_confirmed());
+
+        entity.accept(100);
+        assert!(entity.is_confirmed());
+        assert_eq!(entity.last_step, 100);
+
+        let confirmed_at = entity.confirmed_at;
+        entity.accept(101);
+        assert_eq!(entity.confirmed_at, confirmed_at);
+    }
+}
@@ -2,51 +2,128 @@ pub mod implementations;
 pub mod interfaces;
 
 use crate::repositories::implementations::{
-    auth::AuthRepositoryImpl, content::ContentRepositoryImpl, member::MemberRepositoryImpl,
+    access_token::AccessTokenRepositoryImpl, audit_log::AuditLogRepositoryImpl,
+    auth::AuthRepositoryImpl, auth_event::AuthEventRepositoryImpl, content::ContentRepositoryImpl,
+    member::MemberRepositoryImpl, one_time_token::OneTimeTokenRepositoryImpl,
+    recovery_code::RecoveryCodeRepositoryImpl, refresh_token::RefreshTokenRepositoryImpl,
+    session::SessionRepositoryImpl, totp::TotpRepositoryImpl,
 };
 use crate::repositories::interfaces::{
-    auth::AuthRepository, content::ContentRepository, member::MemberRepository,
+    access_token::AccessTokenRepository, audit_log::AuditLogRepository, auth::AuthRepository,
+    auth_event::AuthEventRepository, content::ContentRepository, member::MemberRepository,
+    one_time_token::OneTimeTokenRepository, recovery_code::RecoveryCodeRepository,
+    refresh_token::RefreshTokenRepository, session::SessionRepository, totp::TotpRepository,
 };
 
 #[derive(Clone)]
 pub struct Repositories {
+    pub access_token_repository: AccessTokenRepositoryImpl,
+    pub audit_log_repository: AuditLogRepositoryImpl,
     pub auth_repository: AuthRepositoryImpl,
+    pub auth_event_repository: AuthEventRepositoryImpl,
     pub content_repository: ContentRepositoryImpl,
     pub member_repository: MemberRepositoryImpl,
+    pub one_time_token_repository: OneTimeTokenRepositoryImpl,
+    pub recovery_code_repository: RecoveryCodeRepositoryImpl,
+    pub refresh_token_repository: RefreshTokenRepositoryImpl,
+    pub session_repository: SessionRepositoryImpl,
+    pub totp_repository: TotpRepositoryImpl,
 }
 
 pub trait RepositoriesExt {
+    type AccessTokenRepository: AccessTokenRepository;
+    type AuditLogRepository: AuditLogRepository;
     type AuthRepository: AuthRepository;
+    type AuthEventRepository: AuthEventRepository;
     type ContentRepository: ContentRepository;
     type MemberRepository: MemberRepository;
-
+    type OneTimeTokenRepository: OneTimeTokenRepository;
+    type RecoveryCodeRepository: RecoveryCodeRepository;
+    type RefreshTokenRepository: RefreshTokenRepository;
+    type SessionRepository: SessionRepository;
+    type TotpRepository: TotpRepository;
+
+    fn access_token_repository(&self) -> &Self::AccessTokenRepository;
+    fn audit_log_repository(&self) -> &Self::AuditLogRepository;
     fn auth_repository(&self) -> &Self::AuthRepository;
+    fn auth_event_repository(&self) -> &Self::AuthEventRepository;
     fn content_repository(&self) -> &Self::ContentRepository;
     fn member_repository(&self) -> &Self::MemberRepository;
+    fn one_time_token_repository(&self) -> &Self::OneTimeTokenRepository;
+    fn recovery_code_repository(&self) -> &Self::RecoveryCodeRepository;
+    fn refresh_token_repository(&self) -> &Self::RefreshTokenRepository;
+    fn session_repository(&self) -> &Self::SessionRepository;
+    fn totp_repository(&self) -> &Self::TotpRepository;
 }
 
 impl RepositoriesExt for Repositories {
+    type AccessTokenRepository = AccessTokenRepositoryImpl;
+    type AuditLogRepository = AuditLogRepositoryImpl;
     type AuthRepository = AuthRepositoryImpl;
+    type AuthEventRepository = AuthEventRepositoryImpl;
     type ContentRepository = ContentRepositoryImpl;
     type MemberRepository = MemberRepositoryImpl;
-
+    type OneTimeTokenRepository = OneTimeTokenRepositoryImpl;
+    type RecoveryCodeRepository = RecoveryCodeRepositoryImpl;
+    type RefreshTokenRepository = RefreshTokenRepositoryImpl;
+    type SessionRepository = SessionRepositoryImpl;
+    type TotpRepository = TotpRepositoryImpl;
+
+    fn access_token_repository(&self) -> &Self::AccessTokenRepository {
+        &self.access_token_repository
+    }
+    fn audit_log_repository(&self) -> &Self::AuditLogRepository {
+        &self.audit_log_repository
+    }
     fn auth_repository(&self) -> &Self::AuthRepository {
         &self.auth_repository
     }
+    fn auth_event_repository(&self) -> &Self::AuthEventRepository {
+        &self.auth_event_repository
+    }
     fn content_repository(&self) -> &Self::ContentRepository {
         &self.content_repository
     }
     fn member_repository(&self) -> &Self::MemberRepository {
         &self.member_repository
     }
+    fn one_time_token_repository(&self) -> &Self::OneTimeTokenRepository {
+        &self.one_time_token_repository
+    }
+    fn recovery_code_repository(&self) -> &Self::RecoveryCodeRepository {
+        &self.recovery_code_repository
+    }
+    fn refresh_token_repository(&self) -> &Self::RefreshTokenRepository {
+        &self.refresh_token_repository
+    }
+    fn session_repository(&self) -> &Self::SessionRepository {
+        &self.session_repository
+    }
+    fn totp_repository(&self) -> &Self::TotpRepository {
+        &self.totp_repository
+    }
+}
+
+impl Default for Repositories {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Repositories {
     pub fn new() -> Self {
         Self {
+            access_token_repository: AccessTokenRepositoryImpl::new(),
+            audit_log_repository: AuditLogRepositoryImpl::new(),
             auth_repository: AuthRepositoryImpl::new(),
+            auth_event_repository: AuthEventRepositoryImpl::new(),
             content_repository: ContentRepositoryImpl::new(),
             member_repository: MemberRepositoryImpl::new(),
+            one_time_token_repository: OneTimeTokenRepositoryImpl::new(),
+            recovery_code_repository: RecoveryCodeRepositoryImpl::new(),
+            refresh_token_repository: RefreshTokenRepositoryImpl::new(),
+            session_repository: SessionRepositoryImpl::new(),
+            totp_repository: TotpRepositoryImpl::new(),
         }
     }
 }
@@ -72,13 +149,16 @@ mod tests {
             password: "test".to_string(),
             name: None,
             email: None,
+            email_verified_at: None,
+            status: models::entities::member::MemberStatus::Active,
+            role: models::entities::member::Role::Member,
             created_at: None,
             updated_at: None,
         };
 
         let result = repository
             .member_repository()
-            .create(&mut *executor, entity.clone())
+            .create(&mut executor, entity.clone())
             .await;
         assert!(result.is_ok());
 
@@ -88,13 +168,13 @@ mod tests {
 
         let result = repository
             .member_repository()
-            .update(&mut *executor, entity.clone())
+            .update(&mut executor, entity.clone())
             .await;
         assert!(result.is_ok());
 
         let result = repository
             .member_repository()
-            .find(&mut *executor, &entity.account.clone())
+            .find(&mut executor, &entity.account.clone())
             .await;
         assert!(result.is_ok());
 
@@ -103,7 +183,7 @@ mod tests {
 
         let result = repository
             .member_repository()
-            .delete(&mut *executor, &entity.account.clone())
+            .delete(&mut executor, &entity.account.clone())
             .await;
         assert!(result.is_ok());
 
@@ -133,7 +213,7 @@ mod tests {
 
         let result = repository
             .auth_repository()
-            .create(&mut *executor, entity.clone())
+            .create(&mut executor, entity.clone())
             .await;
         assert!(result.is_ok());
 
@@ -142,13 +222,13 @@ mod tests {
 
         let result = repository
             .auth_repository()
-            .update(&mut *executor, entity.clone())
+            .update(&mut executor, entity.clone())
             .await;
         assert!(result.is_ok());
 
         let result = repository
             .auth_repository()
-            .find(&mut *executor, &entity.account)
+            .find(&mut executor, &entity.account)
             .await;
         assert!(result.is_ok());
 
@@ -157,7 +237,7 @@ mod tests {
 
         let result = repository
             .auth_repository()
-            .delete(&mut *executor, &entity.account)
+            .delete(&mut executor, &entity.account)
             .await;
         assert!(result.is_ok());
 
@@ -186,7 +266,7 @@ mod tests {
 
         let result = repository
             .content_repository()
-            .create(&mut *executor, entity.clone())
+            .create(&mut executor, entity.clone())
             .await;
         assert!(result.is_ok());
 
@@ -196,13 +276,13 @@ mod tests {
 
         let result = repository
             .content_repository()
-            .update(&mut *executor, entity.clone())
+            .update(&mut executor, entity.clone())
             .await;
         assert!(result.is_ok());
 
         let result = repository
             .content_repository()
-            .find(&mut *executor, entity.content_id)
+            .find(&mut executor, entity.content_id)
             .await;
         assert!(result.is_ok());
 
@@ -211,7 +291,7 @@ mod tests {
 
         let result = repository
             .content_repository()
-            .delete(&mut *executor, entity.content_id)
+            .delete(&mut executor, entity.content_id)
             .await;
         assert!(result.is_ok());
 
@@ -0,0 +1,213 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::access_token::AccessTokenEntity;
+use crate::repositories::interfaces::access_token::AccessTokenRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct AccessTokenRepositoryImpl;
+
+impl Default for AccessTokenRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AccessTokenRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[rustfmt::skip]
+#[async_trait]
+impl AccessTokenRepository for AccessTokenRepositoryImpl {
+    async fn create(&self, executor: &mut DbExecutor, entity: AccessTokenEntity) -> Result<AccessTokenEntity, AppError> {
+        Ok(
+            sqlx::query_as::<_, AccessTokenEntity>(
+                "INSERT INTO access_token (token_id, account, name, token_hash, scopes, expired_at) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
+            )
+            .bind(&entity.token_id)
+            .bind(&entity.account)
+            .bind(&entity.name)
+            .bind(&entity.token_hash)
+            .bind(&entity.scopes)
+            .bind(entity.expired_at)
+            .fetch_one(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn find(&self, executor: &mut DbExecutor, token_id: &str) -> Result<Option<AccessTokenEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, AccessTokenEntity>("SELECT * FROM access_token WHERE token_id = $1")
+                .bind(token_id)
+                .fetch_optional(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn find_by_hash(&self, executor: &mut DbExecutor, token_hash: &str) -> Result<Option<AccessTokenEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, AccessTokenEntity>("SELECT * FROM access_token WHERE token_hash = $1")
+                .bind(token_hash)
+                .fetch_optional(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn list(&self, executor: &mut DbExecutor, account: &str) -> Result<Vec<AccessTokenEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, AccessTokenEntity>("SELECT * FROM access_token WHERE account = $1 ORDER BY created_at DESC")
+                .bind(account)
+                .fetch_all(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn touch(&self, executor: &mut DbExecutor, token_id: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("UPDATE access_token SET last_used_at = $2 WHERE token_id = $1")
+                .bind(token_id)
+                .bind(chrono::Utc::now())
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn delete(&self, executor: &mut DbExecutor, token_id: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM access_token WHERE token_id = $1")
+                .bind(token_id)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM access_token WHERE account = $1")
+                .bind(account)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons;
+    use crate::models::entities::access_token::Scope;
+
+    #[tokio::test]
+    async fn test_access_token_repository_create() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = AccessTokenRepositoryImpl::new();
+
+        let entity = AccessTokenEntity::new(
+            "token",
+            "account",
+            "ci",
+            "hash",
+            &[Scope::ContentRead],
+            None,
+        );
+
+        let mut executor = pool.begin().await.unwrap();
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert_eq!(result.token_id, "token".to_string());
+        assert_eq!(result.scopes(), vec![Scope::ContentRead]);
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_access_token_repository_find() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = AccessTokenRepositoryImpl::new();
+
+        let entity = AccessTokenEntity::new(
+            "token",
+            "account",
+            "ci",
+            "hash",
+            &[Scope::ContentRead],
+            None,
+        );
+
+        let mut executor = pool.begin().await.unwrap();
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let result = repository.find(&mut executor, "token").await;
+        assert!(result.unwrap().is_some());
+
+        let result = repository.find_by_hash(&mut executor, "hash").await;
+        assert!(result.unwrap().is_some());
+
+        let result = repository.find_by_hash(&mut executor, "unknown").await;
+        assert!(result.unwrap().is_none());
+
+        let result = repository.touch(&mut executor, "token").await;
+        assert_eq!(result.unwrap(), 1);
+
+        let result = repository.find(&mut executor, "token").await;
+        assert!(result.unwrap().unwrap().last_used_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_access_token_repository_delete() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = AccessTokenRepositoryImpl::new();
+
+        let mut executor = pool.begin().await.unwrap();
+
+        for (token_id, token_hash) in [
+            ("token1", "hash1"),
+            ("token2", "hash2"),
+            ("token3", "hash3"),
+        ] {
+            let entity = AccessTokenEntity::new(
+                token_id,
+                "account",
+                token_id,
+                token_hash,
+                &[Scope::ContentRead],
+                None,
+            );
+            let result = repository.create(&mut executor, entity).await;
+            assert!(result.is_ok());
+        }
+
+        let result = repository.list(&mut executor, "account").await;
+        assert_eq!(result.unwrap().len(), 3);
+
+        let result = repository.delete(&mut executor, "token1").await;
+        assert_eq!(result.unwrap(), 1);
+
+        let result = repository.delete_by_account(&mut executor, "account").await;
+        assert_eq!(result.unwrap(), 2);
+
+        let result = repository.list(&mut executor, "account").await;
+        assert!(result.unwrap().is_empty());
+    }
+}
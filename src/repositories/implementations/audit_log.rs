@@ -0,0 +1,79 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::audit_log::AuditLogEntity;
+use crate::repositories::interfaces::audit_log::AuditLogRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct AuditLogRepositoryImpl;
+
+impl Default for AuditLogRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuditLogRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[rustfmt::skip]
+#[async_trait]
+impl AuditLogRepository for AuditLogRepositoryImpl {
+    async fn create(&self, executor: &mut DbExecutor, entity: AuditLogEntity) -> Result<AuditLogEntity, AppError> {
+        Ok(
+            sqlx::query_as::<_, AuditLogEntity>(
+                "INSERT INTO audit_log (actor, action, target, detail) VALUES ($1, $2, $3, $4) RETURNING *",
+            )
+            .bind(&entity.actor)
+            .bind(&entity.action)
+            .bind(&entity.target)
+            .bind(&entity.detail)
+            .fetch_one(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn list_by_target(&self, executor: &mut DbExecutor, target: &str) -> Result<Vec<AuditLogEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, AuditLogEntity>("SELECT * FROM audit_log WHERE target = $1 ORDER BY audit_id DESC")
+                .bind(target)
+                .fetch_all(&mut *executor)
+                .await?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons;
+
+    #[tokio::test]
+    async fn test_audit_log_repository() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = AuditLogRepositoryImpl::new();
+
+        let mut executor = pool.begin().await.unwrap();
+
+        for target in ["account1", "account1", "account2"] {
+            let entity = AuditLogEntity::new("admin", "unlock", target, None);
+            let result = repository.create(&mut executor, entity).await;
+            assert!(result.is_ok());
+            assert!(result.unwrap().audit_id > 0);
+        }
+
+        let result = repository.list_by_target(&mut executor, "account1").await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert_eq!(result.len(), 2);
+        assert!(result[0].audit_id > result[1].audit_id);
+        assert_eq!(result[0].actor, "admin".to_string());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::commons::types::{BoxError, DbExecutor};
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
 use crate::models::entities::auth::AuthEntity;
 use crate::repositories::interfaces::auth::AuthRepository;
 use async_trait::async_trait;
@@ -6,6 +7,12 @@ use async_trait::async_trait;
 #[derive(Clone)]
 pub struct AuthRepositoryImpl;
 
+impl Default for AuthRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl AuthRepositoryImpl {
     pub fn new() -> Self {
         Self {}
@@ -15,24 +22,25 @@ impl AuthRepositoryImpl {
 #[rustfmt::skip]
 #[async_trait]
 impl AuthRepository for AuthRepositoryImpl {
-    async fn create(&self, executor: &mut DbExecutor, entity: AuthEntity) -> Result<AuthEntity, BoxError> {
+    async fn create(&self, executor: &mut DbExecutor, entity: AuthEntity) -> Result<AuthEntity, AppError> {
         Ok(
             sqlx::query_as::<_, AuthEntity>(
-                "INSERT INTO auth (account, issued_tm, expired_tm, jwt_id, missmatch, login_at, prev_login_at) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING *",
+                "INSERT INTO auth (account, issued_tm, expired_tm, jwt_id, missmatch, challenge_at, login_at, prev_login_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING *",
             )
             .bind(&entity.account)
-            .bind(&entity.issued_tm)
-            .bind(&entity.expired_tm)
+            .bind(entity.issued_tm)
+            .bind(entity.expired_tm)
             .bind(&entity.jwt_id)
-            .bind(&entity.missmatch)
-            .bind(&entity.login_at)
-            .bind(&entity.prev_login_at)
+            .bind(entity.missmatch)
+            .bind(entity.challenge_at)
+            .bind(entity.login_at)
+            .bind(entity.prev_login_at)
             .fetch_one(&mut *executor)
             .await?,
         )
     }
 
-    async fn find(&self, executor: &mut DbExecutor, account: &str) -> Result<Option<AuthEntity>, BoxError> {
+    async fn find(&self, executor: &mut DbExecutor, account: &str) -> Result<Option<AuthEntity>, AppError> {
         Ok(
             sqlx::query_as::<_, AuthEntity>("SELECT * FROM auth WHERE account = $1")
                 .bind(account)
@@ -41,24 +49,25 @@ impl AuthRepository for AuthRepositoryImpl {
         )
     }
 
-    async fn update(&self, executor: &mut DbExecutor, entity: AuthEntity) -> Result<Option<AuthEntity>, BoxError> {
+    async fn update(&self, executor: &mut DbExecutor, entity: AuthEntity) -> Result<Option<AuthEntity>, AppError> {
         Ok(
             sqlx::query_as::<_, AuthEntity>(
-                "UPDATE auth SET issued_tm = $2, expired_tm = $3, jwt_id = $4, missmatch = $5, login_at = $6, prev_login_at = $7 WHERE account = $1 RETURNING *",
+                "UPDATE auth SET issued_tm = $2, expired_tm = $3, jwt_id = $4, missmatch = $5, challenge_at = $6, login_at = $7, prev_login_at = $8 WHERE account = $1 RETURNING *",
             )
             .bind(&entity.account)
-            .bind(&entity.issued_tm)
-            .bind(&entity.expired_tm)
+            .bind(entity.issued_tm)
+            .bind(entity.expired_tm)
             .bind(&entity.jwt_id)
-            .bind(&entity.missmatch)
-            .bind(&entity.login_at)
-            .bind(&entity.prev_login_at)
+            .bind(entity.missmatch)
+            .bind(entity.challenge_at)
+            .bind(entity.login_at)
+            .bind(entity.prev_login_at)
             .fetch_optional(&mut *executor)
             .await?,
         )
     }
 
-    async fn delete(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, BoxError> {
+    async fn delete(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
         Ok(
             sqlx::query("DELETE FROM auth WHERE account = $1")
                 .bind(account)
@@ -97,7 +106,7 @@ mod tests {
 
         let mut executor = pool.begin().await.unwrap();
 
-        let result = repository.create(&mut *executor, entity.clone()).await;
+        let result = repository.create(&mut executor, entity.clone()).await;
         println!("result: {:#?}", result);
         assert!(result.is_ok());
 
@@ -105,7 +114,7 @@ mod tests {
 
         assert_eq!(result.account.clone(), account.clone());
 
-        let result = repository.create(&mut *executor, entity.clone()).await;
+        let result = repository.create(&mut executor, entity.clone()).await;
         assert!(result.is_err());
     }
 
@@ -131,10 +140,10 @@ mod tests {
 
         let mut executor = pool.begin().await.unwrap();
 
-        let result = repository.create(&mut *executor, entity.clone()).await;
+        let result = repository.create(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
-        let result = repository.find(&mut *executor, &account).await;
+        let result = repository.find(&mut executor, &account).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -143,7 +152,7 @@ mod tests {
         let result = result.unwrap();
         assert_eq!(result.account.clone(), account.clone());
 
-        let result = repository.find(&mut *executor, "unknown").await;
+        let result = repository.find(&mut executor, "unknown").await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -172,13 +181,14 @@ mod tests {
 
         let mut executor = pool.begin().await.unwrap();
 
-        let result = repository.create(&mut *executor, entity.clone()).await;
+        let result = repository.create(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
         let mut entity = result.unwrap();
         entity.missmatch = 9;
+        entity.challenge_at = Some(Utc::now());
 
-        let result = repository.update(&mut *executor, entity.clone()).await;
+        let result = repository.update(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -186,10 +196,11 @@ mod tests {
 
         let result = result.unwrap();
         assert_eq!(result.missmatch, 9);
+        assert_eq!(result.challenge_at, entity.challenge_at);
 
         entity.account = "unknown".to_string();
 
-        let result = repository.update(&mut *executor, entity.clone()).await;
+        let result = repository.update(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -218,16 +229,16 @@ mod tests {
 
         let mut executor = pool.begin().await.unwrap();
 
-        let result = repository.create(&mut *executor, entity.clone()).await;
+        let result = repository.create(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
-        let result = repository.delete(&mut *executor, &account).await;
+        let result = repository.delete(&mut executor, &account).await;
         assert!(result.is_ok());
         let count = result.unwrap();
 
         assert_eq!(count, 1);
 
-        let result = repository.delete(&mut *executor, &account).await;
+        let result = repository.delete(&mut executor, &account).await;
         assert!(result.is_ok());
         let count = result.unwrap();
 
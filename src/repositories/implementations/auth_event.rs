@@ -0,0 +1,166 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::dtos::pagination;
+use crate::models::entities::auth_event::{AuthEventEntity, AuthEventFilter};
+use crate::repositories::interfaces::auth_event::AuthEventRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct AuthEventRepositoryImpl;
+
+impl Default for AuthEventRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuthEventRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+// every filter is optional, a NULL parameter matches all rows
+const FILTER: &str = "($1 IS NULL OR account = $1) AND ($2 IS NULL OR event_type = $2) AND ($3 IS NULL OR ip_address = $3) AND ($4 IS NULL OR datetime(created_at) >= datetime($4)) AND ($5 IS NULL OR datetime(created_at) < datetime($5))";
+
+#[rustfmt::skip]
+#[async_trait]
+impl AuthEventRepository for AuthEventRepositoryImpl {
+    async fn create(&self, executor: &mut DbExecutor, entity: AuthEventEntity) -> Result<AuthEventEntity, AppError> {
+        Ok(
+            sqlx::query_as::<_, AuthEventEntity>(
+                "INSERT INTO auth_event (account, event_type, ip_address, user_agent) VALUES ($1, $2, $3, $4) RETURNING *",
+            )
+            .bind(&entity.account)
+            .bind(entity.event_type)
+            .bind(&entity.ip_address)
+            .bind(&entity.user_agent)
+            .fetch_one(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn list(&self, executor: &mut DbExecutor, filter: &AuthEventFilter, page: i32, size: i32) -> Result<Vec<AuthEventEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, AuthEventEntity>(&format!("SELECT * FROM auth_event WHERE {} ORDER BY event_id DESC LIMIT $6 OFFSET $7", FILTER))
+                .bind(&filter.account)
+                .bind(filter.event_type)
+                .bind(&filter.ip_address)
+                .bind(filter.from)
+                .bind(filter.to)
+                .bind(size)
+                .bind(pagination::offset(page, size))
+                .fetch_all(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn list_all(&self, executor: &mut DbExecutor, filter: &AuthEventFilter) -> Result<Vec<AuthEventEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, AuthEventEntity>(&format!("SELECT * FROM auth_event WHERE {} ORDER BY event_id DESC", FILTER))
+                .bind(&filter.account)
+                .bind(filter.event_type)
+                .bind(&filter.ip_address)
+                .bind(filter.from)
+                .bind(filter.to)
+                .fetch_all(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn count(&self, executor: &mut DbExecutor, filter: &AuthEventFilter) -> Result<i64, AppError> {
+        Ok(
+            sqlx::query_scalar(&format!("SELECT COUNT(*) FROM auth_event WHERE {}", FILTER))
+                .bind(&filter.account)
+                .bind(filter.event_type)
+                .bind(&filter.ip_address)
+                .bind(filter.from)
+                .bind(filter.to)
+                .fetch_one(&mut *executor)
+                .await?,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons;
+    use crate::models::entities::auth_event::AuthEventType;
+
+    #[tokio::test]
+    async fn test_auth_event_repository() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = AuthEventRepositoryImpl::new();
+
+        let mut executor = pool.begin().await.unwrap();
+
+        for (account, event_type, ip_address) in [
+            ("account1", AuthEventType::SigninFailed, "10.0.0.1"),
+            ("account1", AuthEventType::Signin, "10.0.0.1"),
+            ("account2", AuthEventType::SigninFailed, "10.0.0.2"),
+        ] {
+            let entity = AuthEventEntity::new(
+                account,
+                event_type,
+                Some(ip_address.to_string()),
+                Some("curl/8.0".to_string()),
+            );
+            let result = repository.create(&mut executor, entity).await;
+            assert!(result.is_ok());
+            assert!(result.unwrap().event_id > 0);
+        }
+
+        let filter = AuthEventFilter {
+            account: Some("account1".to_string()),
+            ..Default::default()
+        };
+        let result = repository.list(&mut executor, &filter, 1, 10).await;
+        let result = result.unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].event_type, AuthEventType::Signin);
+
+        let filter = AuthEventFilter {
+            event_type: Some(AuthEventType::SigninFailed),
+            ..Default::default()
+        };
+        let result = repository.count(&mut executor, &filter).await;
+        assert_eq!(result.unwrap(), 2);
+
+        let filter = AuthEventFilter {
+            ip_address: Some("10.0.0.2".to_string()),
+            from: Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+            to: Some(chrono::Utc::now() + chrono::Duration::minutes(1)),
+            ..Default::default()
+        };
+        let result = repository.list(&mut executor, &filter, 1, 10).await;
+        assert_eq!(result.unwrap().len(), 1);
+
+        let filter = AuthEventFilter {
+            to: Some(chrono::Utc::now() - chrono::Duration::minutes(1)),
+            ..Default::default()
+        };
+        let result = repository.count(&mut executor, &filter).await;
+        assert_eq!(result.unwrap(), 0);
+
+        let result = repository
+            .list(&mut executor, &AuthEventFilter::default(), 2, 2)
+            .await;
+        assert_eq!(result.unwrap().len(), 1);
+
+        let filter = AuthEventFilter {
+            account: Some("account1".to_string()),
+            ..Default::default()
+        };
+        let result = repository.list_all(&mut executor, &filter).await;
+        assert_eq!(result.unwrap().len(), 2);
+
+        let result = sqlx::query("DELETE FROM auth_event")
+            .execute(&mut *executor)
+            .await;
+        assert!(result.is_err());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::commons::types::{BoxError, DbExecutor};
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::dtos::pagination;
 use crate::models::entities::content::ContentEntity;
 use crate::repositories::interfaces::content::ContentRepository;
 use async_trait::async_trait;
@@ -6,22 +8,41 @@ use async_trait::async_trait;
 #[derive(Clone)]
 pub struct ContentRepositoryImpl;
 
+impl Default for ContentRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ContentRepositoryImpl {
     pub fn new() -> Self {
         Self {}
     }
 }
 
+// the title is a literal prefix, so LIKE wildcards in it must not match anything
+fn like_prefix(title: &str) -> String {
+    let mut pattern = String::with_capacity(title.len() + 1);
+    for c in title.chars() {
+        if matches!(c, '\\' | '%' | '_') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
 #[rustfmt::skip]
 #[async_trait]
 impl ContentRepository for ContentRepositoryImpl {
-    async fn create(&self, executor: &mut DbExecutor, entity: ContentEntity) -> Result<ContentEntity, BoxError> {
+    async fn create(&self, executor: &mut DbExecutor, entity: ContentEntity) -> Result<ContentEntity, AppError> {
         Ok(
             sqlx::query_as::<_, ContentEntity>(
                 "INSERT INTO content (account, post_at, title, body) VALUES ($1, $2, $3, $4) RETURNING *",
             )
             .bind(&entity.account)
-            .bind(&entity.post_at)
+            .bind(entity.post_at)
             .bind(&entity.title)
             .bind(&entity.body)
             .fetch_one(&mut *executor)
@@ -29,7 +50,7 @@ impl ContentRepository for ContentRepositoryImpl {
         )
     }
 
-    async fn find(&self, executor: &mut DbExecutor, content_id: i64) -> Result<Option<ContentEntity>, BoxError> {
+    async fn find(&self, executor: &mut DbExecutor, content_id: i64) -> Result<Option<ContentEntity>, AppError> {
         Ok(
             sqlx::query_as::<_, ContentEntity>("SELECT * FROM content WHERE content_id = $1")
                 .bind(content_id)
@@ -38,14 +59,14 @@ impl ContentRepository for ContentRepositoryImpl {
         )
     }
 
-    async fn update(&self, executor: &mut DbExecutor, entity: ContentEntity) -> Result<Option<ContentEntity>, BoxError> {
+    async fn update(&self, executor: &mut DbExecutor, entity: ContentEntity) -> Result<Option<ContentEntity>, AppError> {
         Ok(
             sqlx::query_as::<_, ContentEntity>(
                 "UPDATE content SET account = $2, post_at = $3, title = $4, body = $5 WHERE content_id = $1 RETURNING *",
             )
-            .bind(&entity.content_id)
+            .bind(entity.content_id)
             .bind(&entity.account)
-            .bind(&entity.post_at)
+            .bind(entity.post_at)
             .bind(&entity.title)
             .bind(&entity.body)
             .fetch_optional(&mut *executor)
@@ -53,7 +74,7 @@ impl ContentRepository for ContentRepositoryImpl {
         )
     }
 
-    async fn delete(&self, executor: &mut DbExecutor, content_id: i64) -> Result<u64, BoxError> {
+    async fn delete(&self, executor: &mut DbExecutor, content_id: i64) -> Result<u64, AppError> {
         Ok(
             sqlx::query("DELETE FROM content WHERE content_id = $1")
                 .bind(content_id)
@@ -63,21 +84,42 @@ impl ContentRepository for ContentRepositoryImpl {
         )
     }
 
-    async fn list(&self, executor: &mut DbExecutor, title: Option<&str>, page: i32, size: i32) -> Result<Vec<ContentEntity>, BoxError> {
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM content WHERE account = $1")
+                .bind(account)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn reassign(&self, executor: &mut DbExecutor, account: &str, to: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("UPDATE content SET account = $2 WHERE account = $1")
+                .bind(account)
+                .bind(to)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn list(&self, executor: &mut DbExecutor, title: Option<&str>, page: i32, size: i32) -> Result<Vec<ContentEntity>, AppError> {
         Ok(
             match title {
                 Some(title) => {
-                    sqlx::query_as::<_, ContentEntity>("SELECT * FROM content WHERE title LIKE $1 || '%' ORDER BY post_at DESC LIMIT $2 OFFSET $3")
-                        .bind(title)
+                    sqlx::query_as::<_, ContentEntity>("SELECT * FROM content WHERE title LIKE $1 ESCAPE '\\' ORDER BY post_at DESC LIMIT $2 OFFSET $3")
+                        .bind(like_prefix(title))
                         .bind(size)
-                        .bind((page - 1) * size)
+                        .bind(pagination::offset(page, size))
                         .fetch_all(&mut *executor)
                         .await?
                 }
                 None => {
                     sqlx::query_as::<_, ContentEntity>("SELECT * FROM content ORDER BY post_at DESC LIMIT $1 OFFSET $2")
                         .bind(size)
-                        .bind((page - 1) * size)
+                        .bind(pagination::offset(page, size))
                         .fetch_all(&mut *executor)
                         .await?
                 }
@@ -86,12 +128,12 @@ impl ContentRepository for ContentRepositoryImpl {
         )
     }
 
-    async fn count(&self, executor: &mut DbExecutor, title: Option<&str>) -> Result<i64, BoxError> {
+    async fn count(&self, executor: &mut DbExecutor, title: Option<&str>) -> Result<i64, AppError> {
         Ok(
             match title {
                 Some(title) => {
-                    sqlx::query_scalar("SELECT COUNT(*) FROM content WHERE title LIKE $1 || '%'")
-                        .bind(title)
+                    sqlx::query_scalar("SELECT COUNT(*) FROM content WHERE title LIKE $1 ESCAPE '\\'")
+                        .bind(like_prefix(title))
                         .fetch_one(&mut *executor)
                         .await?
                 }
@@ -103,6 +145,15 @@ impl ContentRepository for ContentRepositoryImpl {
             }
         )
     }
+
+    async fn list_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<Vec<ContentEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, ContentEntity>("SELECT * FROM content WHERE account = $1 ORDER BY post_at DESC")
+                .bind(account)
+                .fetch_all(&mut *executor)
+                .await?,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -265,6 +316,48 @@ mod tests {
         assert_eq!(result, 0);
     }
 
+    #[tokio::test]
+    async fn test_content_repository_delete_by_account() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+        let mut executor = pool.begin().await.unwrap();
+        let repository = ContentRepositoryImpl::new();
+
+        for account in ["test", "test", "test", "other"] {
+            let entity = ContentEntity {
+                content_id: 0,
+                account: account.to_string(),
+                post_at: Utc::now(),
+                title: "test".to_string(),
+                body: "test".to_string(),
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repository.create(&mut executor, entity).await;
+            assert!(result.is_ok());
+        }
+
+        let result = repository.list_by_account(&mut executor, "test").await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().len(), 3);
+
+        let result = repository.reassign(&mut executor, "test", "deleted").await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 3);
+
+        let result = repository.delete_by_account(&mut executor, "deleted").await;
+
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 3);
+
+        let result = repository.count(&mut executor, None).await;
+
+        assert_eq!(result.unwrap(), 1);
+    }
+
     #[tokio::test]
     async fn test_content_repository_list() {
         let pool = commons::setup::initialize_db("sqlite::memory:")
@@ -284,7 +377,7 @@ mod tests {
         };
 
         for _ in 0..10 {
-            let result = repository.create(&mut *executor, entity.clone()).await;
+            let result = repository.create(&mut executor, entity.clone()).await;
             assert!(result.is_ok());
         }
 
@@ -299,36 +392,83 @@ mod tests {
         };
 
         for _ in 0..10 {
-            let result = repository.create(&mut *executor, entity.clone()).await;
+            let result = repository.create(&mut executor, entity.clone()).await;
             assert!(result.is_ok());
         }
         executor.commit().await.unwrap();
 
         let mut executor = pool.acquire().await.unwrap();
 
-        let result = repository.count(&mut *executor, None).await;
+        let result = repository.count(&mut executor, None).await;
         assert!(result.is_ok());
         let result = result.unwrap();
         assert_eq!(result, 20);
 
-        let result = repository.count(&mut *executor, Some("title")).await;
+        let result = repository.count(&mut executor, Some("title")).await;
         assert!(result.is_ok());
         let result = result.unwrap();
         assert_eq!(result, 10);
 
-        let result = repository.list(&mut *executor, Some("tit"), 1, 10).await;
+        let result = repository.list(&mut executor, Some("tit"), 1, 10).await;
         assert!(result.is_ok());
         let result = result.unwrap();
         assert_eq!(result.len(), 10);
 
-        let result = repository.list(&mut *executor, None, 2, 10).await;
+        let result = repository.list(&mut executor, None, 2, 10).await;
         assert!(result.is_ok());
         let result = result.unwrap();
         assert_eq!(result.len(), 10);
 
-        let result = repository.list(&mut *executor, None, 3, 10).await;
+        let result = repository.list(&mut executor, None, 3, 10).await;
         assert!(result.is_ok());
         let result = result.unwrap();
         assert_eq!(result.len(), 0);
     }
+
+    #[tokio::test]
+    async fn test_content_repository_list_wildcard() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+        let mut executor = pool.begin().await.unwrap();
+        let repository = ContentRepositoryImpl::new();
+
+        for title in ["100% done", "1000 done", "a_b", "axb", "a\\b"] {
+            let entity = ContentEntity {
+                content_id: 0,
+                account: "test".to_string(),
+                post_at: Utc::now(),
+                title: title.to_string(),
+                body: "test".to_string(),
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repository.create(&mut executor, entity).await;
+            assert!(result.is_ok());
+        }
+
+        for (prefix, expected) in [
+            ("100%", vec!["100% done"]),
+            ("a_", vec!["a_b"]),
+            ("a\\", vec!["a\\b"]),
+            ("%", vec![]),
+            ("", vec!["100% done", "1000 done", "a_b", "axb", "a\\b"]),
+        ] {
+            let count = repository.count(&mut executor, Some(prefix)).await.unwrap();
+            assert_eq!(count, expected.len() as i64);
+
+            let result = repository
+                .list(&mut executor, Some(prefix), 1, 10)
+                .await
+                .unwrap();
+            let mut titles: Vec<_> = result
+                .iter()
+                .map(|content| content.title.as_str())
+                .collect();
+            titles.sort();
+            let mut expected = expected;
+            expected.sort();
+            assert_eq!(titles, expected);
+        }
+    }
 }
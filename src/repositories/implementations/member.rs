@@ -1,11 +1,18 @@
-use crate::commons::types::{BoxError, DbExecutor};
-use crate::models::entities::member::MemberEntity;
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::member::{MemberEntity, MemberStatus, Role};
 use crate::repositories::interfaces::member::MemberRepository;
 use async_trait::async_trait;
 
 #[derive(Clone)]
 pub struct MemberRepositoryImpl;
 
+impl Default for MemberRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MemberRepositoryImpl {
     pub fn new() -> Self {
         Self {}
@@ -15,21 +22,23 @@ impl MemberRepositoryImpl {
 #[rustfmt::skip]
 #[async_trait]
 impl MemberRepository for MemberRepositoryImpl {
-    async fn create(&self, executor: &mut DbExecutor, entity: MemberEntity) -> Result<MemberEntity, BoxError> {
+    async fn create(&self, executor: &mut DbExecutor, entity: MemberEntity) -> Result<MemberEntity, AppError> {
         Ok(
             sqlx::query_as::<_, MemberEntity>(
-                "INSERT INTO member (account, password, name, email) VALUES ($1, $2, $3, $4) RETURNING *",
+                "INSERT INTO member (account, password, name, email, status, role) VALUES ($1, $2, $3, $4, $5, $6) RETURNING *",
             )
             .bind(&entity.account)
             .bind(&entity.password)
             .bind(&entity.name)
             .bind(&entity.email)
+            .bind(entity.status)
+            .bind(entity.role)
             .fetch_one(&mut *executor)
             .await?,
         )
     }
 
-    async fn find(&self, executor: &mut DbExecutor, account: &str) -> Result<Option<MemberEntity>, BoxError> {
+    async fn find(&self, executor: &mut DbExecutor, account: &str) -> Result<Option<MemberEntity>, AppError> {
         Ok(
             sqlx::query_as::<_, MemberEntity>("SELECT * FROM member WHERE account = $1")
                 .bind(account)
@@ -38,21 +47,75 @@ impl MemberRepository for MemberRepositoryImpl {
         )
     }
 
-    async fn update(&self, executor: &mut DbExecutor, entity: MemberEntity) -> Result<Option<MemberEntity>, BoxError> {
+    async fn list_by_status(&self, executor: &mut DbExecutor, status: MemberStatus) -> Result<Vec<MemberEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, MemberEntity>("SELECT * FROM member WHERE status = $1 ORDER BY created_at, account")
+                .bind(status)
+                .fetch_all(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn count(&self, executor: &mut DbExecutor) -> Result<i64, AppError> {
+        Ok(
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM member")
+                .fetch_one(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn update(&self, executor: &mut DbExecutor, entity: MemberEntity) -> Result<Option<MemberEntity>, AppError> {
         Ok(
             sqlx::query_as::<_, MemberEntity>(
-                "UPDATE member SET password = $2, name = $3, email = $4, updated_at = CURRENT_TIMESTAMP WHERE account = $1 RETURNING *",
+                "UPDATE member SET password = $2, name = $3, email = $4, email_verified_at = $5, updated_at = CURRENT_TIMESTAMP WHERE account = $1 RETURNING *",
             )
             .bind(&entity.account)
             .bind(&entity.password)
             .bind(&entity.name)
             .bind(&entity.email)
+            .bind(entity.email_verified_at)
             .fetch_optional(&mut *executor)
             .await?,
         )
     }
 
-    async fn delete(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, BoxError> {
+    // leaves updated_at alone, a rehash is not a profile change
+    async fn update_password(&self, executor: &mut DbExecutor, account: &str, password: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("UPDATE member SET password = $2 WHERE account = $1")
+                .bind(account)
+                .bind(password)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn update_status(&self, executor: &mut DbExecutor, account: &str, status: MemberStatus) -> Result<Option<MemberEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, MemberEntity>(
+                "UPDATE member SET status = $2, updated_at = CURRENT_TIMESTAMP WHERE account = $1 RETURNING *",
+            )
+            .bind(account)
+            .bind(status)
+            .fetch_optional(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn update_role(&self, executor: &mut DbExecutor, account: &str, role: Role) -> Result<Option<MemberEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, MemberEntity>(
+                "UPDATE member SET role = $2, updated_at = CURRENT_TIMESTAMP WHERE account = $1 RETURNING *",
+            )
+            .bind(account)
+            .bind(role)
+            .fetch_optional(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn delete(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
         Ok(
             sqlx::query("DELETE FROM member WHERE account = $1")
                 .bind(account)
@@ -84,19 +147,25 @@ mod tests {
             password: password.clone(),
             name: None,
             email: None,
+            email_verified_at: None,
+            status: MemberStatus::Active,
+            role: Role::Member,
             created_at: None,
             updated_at: None,
         };
 
         let mut executor = pool.begin().await.unwrap();
 
-        let result = repository.create(&mut *executor, entity.clone()).await;
+        let result = repository.create(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
 
         assert_eq!(result.account.clone(), account.clone());
         assert_eq!(result.password.clone(), password.clone());
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
     }
 
     #[tokio::test]
@@ -115,16 +184,19 @@ mod tests {
             password: password.clone(),
             name: None,
             email: None,
+            email_verified_at: None,
+            status: MemberStatus::Active,
+            role: Role::Member,
             created_at: None,
             updated_at: None,
         };
 
         let mut executor = pool.begin().await.unwrap();
 
-        let result = repository.create(&mut *executor, entity.clone()).await;
+        let result = repository.create(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
-        let result = repository.find(&mut *executor, &account).await;
+        let result = repository.find(&mut executor, &account).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -135,7 +207,7 @@ mod tests {
         assert_eq!(result.account.clone(), account.clone());
         assert_eq!(result.password.clone(), password.clone());
 
-        let result = repository.find(&mut *executor, "account2").await;
+        let result = repository.find(&mut executor, "account2").await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -158,20 +230,23 @@ mod tests {
             password: password.clone(),
             name: None,
             email: None,
+            email_verified_at: None,
+            status: MemberStatus::Active,
+            role: Role::Member,
             created_at: None,
             updated_at: None,
         };
 
         let mut executor = pool.begin().await.unwrap();
 
-        let result = repository.create(&mut *executor, entity.clone()).await;
+        let result = repository.create(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
         let mut entity = result.unwrap();
         entity.name = Some("name".to_string());
         entity.email = Some("email".to_string());
 
-        let result = repository.update(&mut *executor, entity.clone()).await;
+        let result = repository.update(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -184,9 +259,17 @@ mod tests {
         assert_eq!(result.name.clone(), Some("name".to_string()));
         assert_eq!(result.email.clone(), Some("email".to_string()));
 
+        let result = repository
+            .update_password(&mut executor, &account, "rehashed")
+            .await;
+        assert_eq!(result.unwrap(), 1);
+
+        let result = repository.find(&mut executor, &account).await;
+        assert_eq!(result.unwrap().unwrap().password, "rehashed".to_string());
+
         entity.account = "account2".to_string();
 
-        let result = repository.update(&mut *executor, entity.clone()).await;
+        let result = repository.update(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -209,25 +292,87 @@ mod tests {
             password: password.clone(),
             name: None,
             email: None,
+            email_verified_at: None,
+            status: MemberStatus::Active,
+            role: Role::Member,
             created_at: None,
             updated_at: None,
         };
 
         let mut executor = pool.begin().await.unwrap();
 
-        let result = repository.create(&mut *executor, entity.clone()).await;
+        let result = repository.create(&mut executor, entity.clone()).await;
         assert!(result.is_ok());
 
-        let result = repository.delete(&mut *executor, &account).await;
+        let result = repository.delete(&mut executor, &account).await;
         assert!(result.is_ok());
         let count = result.unwrap();
 
         assert_eq!(count, 1);
 
-        let result = repository.delete(&mut *executor, "account2").await;
+        let result = repository.delete(&mut executor, "account2").await;
         assert!(result.is_ok());
         let count = result.unwrap();
 
         assert_eq!(count, 0);
     }
+
+    #[tokio::test]
+    async fn test_member_repository_status() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = MemberRepositoryImpl::new();
+
+        let mut executor = pool.begin().await.unwrap();
+
+        for (account, status) in [
+            ("account1", MemberStatus::Active),
+            ("account2", MemberStatus::Pending),
+            ("account3", MemberStatus::Pending),
+        ] {
+            let entity = MemberEntity {
+                account: account.to_string(),
+                password: "password".to_string(),
+                name: None,
+                email: None,
+                email_verified_at: None,
+                status,
+                role: Role::Member,
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repository.create(&mut executor, entity).await;
+            assert!(result.is_ok());
+        }
+
+        let result = repository.count(&mut executor).await;
+        assert_eq!(result.unwrap(), 3);
+
+        let result = repository
+            .list_by_status(&mut executor, MemberStatus::Pending)
+            .await;
+        assert_eq!(result.unwrap().len(), 2);
+
+        let result = repository
+            .update_status(&mut executor, "account2", MemberStatus::Active)
+            .await;
+        assert_eq!(result.unwrap().unwrap().status, MemberStatus::Active);
+
+        let result = repository
+            .list_by_status(&mut executor, MemberStatus::Pending)
+            .await;
+        assert_eq!(result.unwrap().len(), 1);
+
+        let result = repository
+            .update_status(&mut executor, "unknown", MemberStatus::Active)
+            .await;
+        assert!(result.unwrap().is_none());
+
+        let result = repository
+            .update_role(&mut executor, "account1", Role::Admin)
+            .await;
+        assert_eq!(result.unwrap().unwrap().role, Role::Admin);
+    }
 }
@@ -0,0 +1,131 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::one_time_token::{OneTimeTokenEntity, TokenPurpose};
+use crate::repositories::interfaces::one_time_token::OneTimeTokenRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct OneTimeTokenRepositoryImpl;
+
+impl Default for OneTimeTokenRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OneTimeTokenRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[rustfmt::skip]
+#[async_trait]
+impl OneTimeTokenRepository for OneTimeTokenRepositoryImpl {
+    async fn create(&self, executor: &mut DbExecutor, entity: OneTimeTokenEntity) -> Result<OneTimeTokenEntity, AppError> {
+        Ok(
+            sqlx::query_as::<_, OneTimeTokenEntity>(
+                "INSERT INTO one_time_token (token_hash, account, purpose, expired_at) VALUES ($1, $2, $3, $4) RETURNING *",
+            )
+            .bind(&entity.token_hash)
+            .bind(&entity.account)
+            .bind(entity.purpose)
+            .bind(entity.expired_at)
+            .fetch_one(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn find(&self, executor: &mut DbExecutor, token_hash: &str) -> Result<Option<OneTimeTokenEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, OneTimeTokenEntity>("SELECT * FROM one_time_token WHERE token_hash = $1")
+                .bind(token_hash)
+                .fetch_optional(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn update(&self, executor: &mut DbExecutor, entity: OneTimeTokenEntity) -> Result<Option<OneTimeTokenEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, OneTimeTokenEntity>(
+                "UPDATE one_time_token SET expired_at = $2, used_at = $3 WHERE token_hash = $1 RETURNING *",
+            )
+            .bind(&entity.token_hash)
+            .bind(entity.expired_at)
+            .bind(entity.used_at)
+            .fetch_optional(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str, purpose: TokenPurpose) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM one_time_token WHERE account = $1 AND purpose = $2")
+                .bind(account)
+                .bind(purpose)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn delete_all_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM one_time_token WHERE account = $1")
+                .bind(account)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons;
+
+    #[tokio::test]
+    async fn test_one_time_token_repository() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = OneTimeTokenRepositoryImpl::new();
+
+        let mut executor = pool.begin().await.unwrap();
+
+        for token_hash in ["hash1", "hash2"] {
+            let entity =
+                OneTimeTokenEntity::new("account", TokenPurpose::PasswordReset, token_hash, 60);
+            let result = repository.create(&mut executor, entity).await;
+            assert!(result.is_ok());
+        }
+
+        let result = repository.find(&mut executor, "hash1").await;
+        let mut entity = result.unwrap().unwrap();
+        assert_eq!(entity.purpose, TokenPurpose::PasswordReset);
+
+        entity.consume();
+        let result = repository.update(&mut executor, entity).await;
+        assert!(result.unwrap().unwrap().used_at.is_some());
+
+        let result = repository
+            .delete_by_account(&mut executor, "account", TokenPurpose::PasswordReset)
+            .await;
+        assert_eq!(result.unwrap(), 2);
+
+        let result = repository.find(&mut executor, "hash2").await;
+        assert!(result.unwrap().is_none());
+
+        let entity =
+            OneTimeTokenEntity::new("account", TokenPurpose::EmailVerification, "hash3", 60);
+        let result = repository.create(&mut executor, entity).await;
+        assert!(result.is_ok());
+
+        let result = repository
+            .delete_all_by_account(&mut executor, "account")
+            .await;
+        assert_eq!(result.unwrap(), 1);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::recovery_code::RecoveryCodeEntity;
+use crate::repositories::interfaces::recovery_code::RecoveryCodeRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct RecoveryCodeRepositoryImpl;
+
+impl Default for RecoveryCodeRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RecoveryCodeRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[rustfmt::skip]
+#[async_trait]
+impl RecoveryCodeRepository for RecoveryCodeRepositoryImpl {
+    async fn create(&self, executor: &mut DbExecutor, entity: RecoveryCodeEntity) -> Result<RecoveryCodeEntity, AppError> {
+        Ok(
+            sqlx::query_as::<_, RecoveryCodeEntity>(
+                "INSERT INTO recovery_code (code_hash, account) VALUES ($1, $2) RETURNING *",
+            )
+            .bind(&entity.code_hash)
+            .bind(&entity.account)
+            .fetch_one(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn find(&self, executor: &mut DbExecutor, code_hash: &str) -> Result<Option<RecoveryCodeEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, RecoveryCodeEntity>("SELECT * FROM recovery_code WHERE code_hash = $1")
+                .bind(code_hash)
+                .fetch_optional(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn update(&self, executor: &mut DbExecutor, entity: RecoveryCodeEntity) -> Result<Option<RecoveryCodeEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, RecoveryCodeEntity>(
+                "UPDATE recovery_code SET used_at = $2 WHERE code_hash = $1 RETURNING *",
+            )
+            .bind(&entity.code_hash)
+            .bind(entity.used_at)
+            .fetch_optional(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM recovery_code WHERE account = $1")
+                .bind(account)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons;
+
+    #[tokio::test]
+    async fn test_recovery_code_repository() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = RecoveryCodeRepositoryImpl::new();
+
+        let mut executor = pool.begin().await.unwrap();
+
+        for code_hash in ["hash1", "hash2"] {
+            let result = repository
+                .create(&mut executor, RecoveryCodeEntity::new("account", code_hash))
+                .await;
+            assert!(result.is_ok());
+        }
+
+        let mut entity = repository
+            .find(&mut executor, "hash1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(entity.is_valid("account"));
+
+        entity.consume();
+        let result = repository.update(&mut executor, entity).await;
+        assert!(result.unwrap().unwrap().used_at.is_some());
+
+        let result = repository.delete_by_account(&mut executor, "account").await;
+        assert_eq!(result.unwrap(), 2);
+
+        let result = repository.find(&mut executor, "hash2").await;
+        assert!(result.unwrap().is_none());
+    }
+}
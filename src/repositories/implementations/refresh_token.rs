@@ -0,0 +1,206 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::refresh_token::RefreshTokenEntity;
+use crate::repositories::interfaces::refresh_token::RefreshTokenRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct RefreshTokenRepositoryImpl;
+
+impl Default for RefreshTokenRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RefreshTokenRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[rustfmt::skip]
+#[async_trait]
+impl RefreshTokenRepository for RefreshTokenRepositoryImpl {
+    async fn create(&self, executor: &mut DbExecutor, entity: RefreshTokenEntity) -> Result<RefreshTokenEntity, AppError> {
+        Ok(
+            sqlx::query_as::<_, RefreshTokenEntity>(
+                "INSERT INTO refresh_token (token_hash, family_id, account, expired_at) VALUES ($1, $2, $3, $4) RETURNING *",
+            )
+            .bind(&entity.token_hash)
+            .bind(&entity.family_id)
+            .bind(&entity.account)
+            .bind(entity.expired_at)
+            .fetch_one(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn find(&self, executor: &mut DbExecutor, token_hash: &str) -> Result<Option<RefreshTokenEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, RefreshTokenEntity>("SELECT * FROM refresh_token WHERE token_hash = $1")
+                .bind(token_hash)
+                .fetch_optional(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn update(&self, executor: &mut DbExecutor, entity: RefreshTokenEntity) -> Result<Option<RefreshTokenEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, RefreshTokenEntity>(
+                "UPDATE refresh_token SET expired_at = $2, rotated_at = $3, revoked_at = $4 WHERE token_hash = $1 RETURNING *",
+            )
+            .bind(&entity.token_hash)
+            .bind(entity.expired_at)
+            .bind(entity.rotated_at)
+            .bind(entity.revoked_at)
+            .fetch_optional(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn revoke_family(&self, executor: &mut DbExecutor, family_id: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("UPDATE refresh_token SET revoked_at = CURRENT_TIMESTAMP WHERE family_id = $1 AND revoked_at IS NULL")
+                .bind(family_id)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn revoke_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("UPDATE refresh_token SET revoked_at = CURRENT_TIMESTAMP WHERE account = $1 AND revoked_at IS NULL")
+                .bind(account)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM refresh_token WHERE account = $1")
+                .bind(account)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons;
+
+    #[tokio::test]
+    async fn test_refresh_token_repository_create() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = RefreshTokenRepositoryImpl::new();
+
+        let entity = RefreshTokenEntity::new("account", "family", "hash");
+
+        let mut executor = pool.begin().await.unwrap();
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert_eq!(result.token_hash, "hash".to_string());
+        assert_eq!(result.family_id, "family".to_string());
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_repository_find() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = RefreshTokenRepositoryImpl::new();
+
+        let entity = RefreshTokenEntity::new("account", "family", "hash");
+
+        let mut executor = pool.begin().await.unwrap();
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let result = repository.find(&mut executor, "hash").await;
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_some());
+
+        let result = repository.find(&mut executor, "unknown").await;
+        assert!(result.is_ok());
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_repository_update() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = RefreshTokenRepositoryImpl::new();
+
+        let entity = RefreshTokenEntity::new("account", "family", "hash");
+
+        let mut executor = pool.begin().await.unwrap();
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let mut entity = result.unwrap();
+        entity.rotate();
+
+        let result = repository.update(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert!(result.is_some());
+        assert!(result.unwrap().rotated_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_repository_revoke() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = RefreshTokenRepositoryImpl::new();
+
+        let mut executor = pool.begin().await.unwrap();
+
+        for (family_id, token_hash) in [
+            ("family1", "hash1"),
+            ("family1", "hash2"),
+            ("family2", "hash3"),
+        ] {
+            let entity = RefreshTokenEntity::new("account", family_id, token_hash);
+            let result = repository.create(&mut executor, entity).await;
+            assert!(result.is_ok());
+        }
+
+        let result = repository.revoke_family(&mut executor, "family1").await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 2);
+
+        let result = repository.find(&mut executor, "hash3").await;
+        assert!(result.unwrap().unwrap().revoked_at.is_none());
+
+        let result = repository.revoke_account(&mut executor, "account").await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1);
+
+        let result = repository.delete_by_account(&mut executor, "account").await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 3);
+    }
+}
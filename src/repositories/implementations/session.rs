@@ -0,0 +1,260 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::session::SessionEntity;
+use crate::repositories::interfaces::session::SessionRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct SessionRepositoryImpl;
+
+impl Default for SessionRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[rustfmt::skip]
+#[async_trait]
+impl SessionRepository for SessionRepositoryImpl {
+    async fn create(&self, executor: &mut DbExecutor, entity: SessionEntity) -> Result<SessionEntity, AppError> {
+        Ok(
+            sqlx::query_as::<_, SessionEntity>(
+                "INSERT INTO session (session_id, account, jwt_id, issued_tm, expired_tm, user_agent, ip_address, last_seen_at) VALUES ($1, $2, $3, $4, $5, $6, $7, $8) RETURNING *",
+            )
+            .bind(&entity.session_id)
+            .bind(&entity.account)
+            .bind(&entity.jwt_id)
+            .bind(entity.issued_tm)
+            .bind(entity.expired_tm)
+            .bind(&entity.user_agent)
+            .bind(&entity.ip_address)
+            .bind(entity.last_seen_at)
+            .fetch_one(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn find(&self, executor: &mut DbExecutor, session_id: &str) -> Result<Option<SessionEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, SessionEntity>("SELECT * FROM session WHERE session_id = $1")
+                .bind(session_id)
+                .fetch_optional(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn find_by_jwt_id(&self, executor: &mut DbExecutor, jwt_id: &str) -> Result<Option<SessionEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, SessionEntity>("SELECT * FROM session WHERE jwt_id = $1")
+                .bind(jwt_id)
+                .fetch_optional(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn list(&self, executor: &mut DbExecutor, account: &str) -> Result<Vec<SessionEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, SessionEntity>("SELECT * FROM session WHERE account = $1 ORDER BY last_seen_at DESC")
+                .bind(account)
+                .fetch_all(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn update(&self, executor: &mut DbExecutor, entity: SessionEntity) -> Result<Option<SessionEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, SessionEntity>(
+                "UPDATE session SET jwt_id = $2, issued_tm = $3, expired_tm = $4, user_agent = $5, ip_address = $6, last_seen_at = $7 WHERE session_id = $1 RETURNING *",
+            )
+            .bind(&entity.session_id)
+            .bind(&entity.jwt_id)
+            .bind(entity.issued_tm)
+            .bind(entity.expired_tm)
+            .bind(&entity.user_agent)
+            .bind(&entity.ip_address)
+            .bind(entity.last_seen_at)
+            .fetch_optional(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn touch(&self, executor: &mut DbExecutor, session_id: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("UPDATE session SET last_seen_at = $2 WHERE session_id = $1")
+                .bind(session_id)
+                .bind(chrono::Utc::now())
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn delete(&self, executor: &mut DbExecutor, session_id: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM session WHERE session_id = $1")
+                .bind(session_id)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM session WHERE account = $1")
+                .bind(account)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+
+    async fn delete_expired(&self, executor: &mut DbExecutor, account: &str, expired_tm: i64) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM session WHERE account = $1 AND expired_tm < $2")
+                .bind(account)
+                .bind(expired_tm)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons;
+    use crate::commons::jwt::Claims;
+
+    fn new_session(session_id: &str, account: &str) -> SessionEntity {
+        let claims = Claims::new(account, *commons::config::JWT_EXPIRATION_SECONDS);
+        SessionEntity::new(
+            session_id,
+            claims,
+            Some("curl/8.0".to_string()),
+            Some("127.0.0.1".to_string()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_session_repository_create() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = SessionRepositoryImpl::new();
+
+        let entity = new_session("session", "account");
+
+        let mut executor = pool.begin().await.unwrap();
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert_eq!(result.session_id, "session".to_string());
+        assert_eq!(result.user_agent, Some("curl/8.0".to_string()));
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+    }
+
+    #[tokio::test]
+    async fn test_session_repository_find() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = SessionRepositoryImpl::new();
+
+        let entity = new_session("session", "account");
+
+        let mut executor = pool.begin().await.unwrap();
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let result = repository.find(&mut executor, "session").await;
+        assert!(result.unwrap().is_some());
+
+        let result = repository
+            .find_by_jwt_id(&mut executor, &entity.jwt_id)
+            .await;
+        assert!(result.unwrap().is_some());
+
+        let result = repository.find(&mut executor, "unknown").await;
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session_repository_update() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = SessionRepositoryImpl::new();
+
+        let entity = new_session("session", "account");
+
+        let mut executor = pool.begin().await.unwrap();
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let mut entity = result.unwrap();
+        let claims = Claims::new("account", *commons::config::JWT_EXPIRATION_SECONDS);
+        entity.refreshed(claims.clone());
+
+        let result = repository.update(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().jwt_id, claims.jti);
+
+        let result = repository.touch(&mut executor, "session").await;
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_session_repository_delete() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = SessionRepositoryImpl::new();
+
+        let mut executor = pool.begin().await.unwrap();
+
+        for session_id in ["session1", "session2", "session3"] {
+            let result = repository
+                .create(&mut executor, new_session(session_id, "account"))
+                .await;
+            assert!(result.is_ok());
+        }
+
+        let result = repository.list(&mut executor, "account").await;
+        assert_eq!(result.unwrap().len(), 3);
+
+        let result = repository.delete(&mut executor, "session1").await;
+        assert_eq!(result.unwrap(), 1);
+
+        let result = repository
+            .delete_expired(&mut executor, "account", chrono::Utc::now().timestamp())
+            .await;
+        assert_eq!(result.unwrap(), 0);
+
+        let result = repository.delete_by_account(&mut executor, "account").await;
+        assert_eq!(result.unwrap(), 2);
+
+        let result = repository.list(&mut executor, "account").await;
+        assert!(result.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::totp::TotpEntity;
+use crate::repositories::interfaces::totp::TotpRepository;
+use async_trait::async_trait;
+
+#[derive(Clone)]
+pub struct TotpRepositoryImpl;
+
+impl Default for TotpRepositoryImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TotpRepositoryImpl {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[rustfmt::skip]
+#[async_trait]
+impl TotpRepository for TotpRepositoryImpl {
+    async fn create(&self, executor: &mut DbExecutor, entity: TotpEntity) -> Result<TotpEntity, AppError> {
+        Ok(
+            sqlx::query_as::<_, TotpEntity>(
+                "INSERT INTO totp (account, secret, last_step, confirmed_at) VALUES ($1, $2, $3, $4) RETURNING *",
+            )
+            .bind(&entity.account)
+            .bind(&entity.secret)
+            .bind(entity.last_step)
+            .bind(entity.confirmed_at)
+            .fetch_one(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn find(&self, executor: &mut DbExecutor, account: &str) -> Result<Option<TotpEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, TotpEntity>("SELECT * FROM totp WHERE account = $1")
+                .bind(account)
+                .fetch_optional(&mut *executor)
+                .await?,
+        )
+    }
+
+    async fn update(&self, executor: &mut DbExecutor, entity: TotpEntity) -> Result<Option<TotpEntity>, AppError> {
+        Ok(
+            sqlx::query_as::<_, TotpEntity>(
+                "UPDATE totp SET secret = $2, last_step = $3, confirmed_at = $4 WHERE account = $1 RETURNING *",
+            )
+            .bind(&entity.account)
+            .bind(&entity.secret)
+            .bind(entity.last_step)
+            .bind(entity.confirmed_at)
+            .fetch_optional(&mut *executor)
+            .await?,
+        )
+    }
+
+    async fn delete(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError> {
+        Ok(
+            sqlx::query("DELETE FROM totp WHERE account = $1")
+                .bind(account)
+                .execute(&mut *executor)
+                .await?
+                .rows_affected(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons;
+
+    #[tokio::test]
+    async fn test_totp_repository() {
+        let pool = commons::setup::initialize_db("sqlite::memory:")
+            .await
+            .unwrap();
+
+        let repository = TotpRepositoryImpl::new();
+
+        let mut executor = pool.begin().await.unwrap();
+
+        let entity = TotpEntity::new("account", "secret");
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(result.is_ok());
+
+        let result = repository.create(&mut executor, entity.clone()).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+
+        let mut entity = repository
+            .find(&mut executor, "account")
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!entity.is_confirmed());
+
+        entity.accept(100);
+        let result = repository.update(&mut executor, entity).await;
+        let result = result.unwrap().unwrap();
+        assert!(result.is_confirmed());
+        assert_eq!(result.last_step, 100);
+
+        let result = repository.delete(&mut executor, "account").await;
+        assert_eq!(result.unwrap(), 1);
+
+        let result = repository.find(&mut executor, "account").await;
+        assert!(result.unwrap().is_none());
+    }
+}
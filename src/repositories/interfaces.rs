@@ -1,3 +1,11 @@
+pub mod access_token;
+pub mod audit_log;
 pub mod auth;
+pub mod auth_event;
 pub mod content;
 pub mod member;
+pub mod one_time_token;
+pub mod recovery_code;
+pub mod refresh_token;
+pub mod session;
+pub mod totp;
@@ -0,0 +1,16 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::access_token::AccessTokenEntity;
+use async_trait::async_trait;
+
+#[rustfmt::skip]
+#[async_trait]
+pub trait AccessTokenRepository: Send + Sync {
+    async fn create(&self, executor: &mut DbExecutor, entity: AccessTokenEntity) -> Result<AccessTokenEntity, AppError>;
+    async fn find(&self, executor: &mut DbExecutor, token_id: &str) -> Result<Option<AccessTokenEntity>, AppError>;
+    async fn find_by_hash(&self, executor: &mut DbExecutor, token_hash: &str) -> Result<Option<AccessTokenEntity>, AppError>;
+    async fn list(&self, executor: &mut DbExecutor, account: &str) -> Result<Vec<AccessTokenEntity>, AppError>;
+    async fn touch(&self, executor: &mut DbExecutor, token_id: &str) -> Result<u64, AppError>;
+    async fn delete(&self, executor: &mut DbExecutor, token_id: &str) -> Result<u64, AppError>;
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError>;
+}
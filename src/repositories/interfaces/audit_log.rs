@@ -0,0 +1,11 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::audit_log::AuditLogEntity;
+use async_trait::async_trait;
+
+#[rustfmt::skip]
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    async fn create(&self, executor: &mut DbExecutor, entity: AuditLogEntity) -> Result<AuditLogEntity, AppError>;
+    async fn list_by_target(&self, executor: &mut DbExecutor, target: &str) -> Result<Vec<AuditLogEntity>, AppError>;
+}
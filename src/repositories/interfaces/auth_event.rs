@@ -0,0 +1,13 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::auth_event::{AuthEventEntity, AuthEventFilter};
+use async_trait::async_trait;
+
+#[rustfmt::skip]
+#[async_trait]
+pub trait AuthEventRepository: Send + Sync {
+    async fn create(&self, executor: &mut DbExecutor, entity: AuthEventEntity) -> Result<AuthEventEntity, AppError>;
+    async fn list(&self, executor: &mut DbExecutor, filter: &AuthEventFilter, page: i32, size: i32) -> Result<Vec<AuthEventEntity>, AppError>;
+    async fn list_all(&self, executor: &mut DbExecutor, filter: &AuthEventFilter) -> Result<Vec<AuthEventEntity>, AppError>;
+    async fn count(&self, executor: &mut DbExecutor, filter: &AuthEventFilter) -> Result<i64, AppError>;
+}
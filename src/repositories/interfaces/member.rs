@@ -1,12 +1,18 @@
-use crate::commons::types::{BoxError, DbExecutor};
-use crate::models::entities::member::MemberEntity;
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::member::{MemberEntity, MemberStatus, Role};
 use async_trait::async_trait;
 
 #[rustfmt::skip]
 #[async_trait]
 pub trait MemberRepository {
-    async fn create(&self, executor: &mut DbExecutor, entity: MemberEntity) -> Result<MemberEntity, BoxError>;
-    async fn find(&self, executor: &mut DbExecutor, account: &str) -> Result<Option<MemberEntity>, BoxError>;
-    async fn update(&self, executor: &mut DbExecutor, entity: MemberEntity) -> Result<Option<MemberEntity>, BoxError>;
-    async fn delete(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, BoxError>;
+    async fn create(&self, executor: &mut DbExecutor, entity: MemberEntity) -> Result<MemberEntity, AppError>;
+    async fn find(&self, executor: &mut DbExecutor, account: &str) -> Result<Option<MemberEntity>, AppError>;
+    async fn list_by_status(&self, executor: &mut DbExecutor, status: MemberStatus) -> Result<Vec<MemberEntity>, AppError>;
+    async fn count(&self, executor: &mut DbExecutor) -> Result<i64, AppError>;
+    async fn update(&self, executor: &mut DbExecutor, entity: MemberEntity) -> Result<Option<MemberEntity>, AppError>;
+    async fn update_password(&self, executor: &mut DbExecutor, account: &str, password: &str) -> Result<u64, AppError>;
+    async fn update_status(&self, executor: &mut DbExecutor, account: &str, status: MemberStatus) -> Result<Option<MemberEntity>, AppError>;
+    async fn update_role(&self, executor: &mut DbExecutor, account: &str, role: Role) -> Result<Option<MemberEntity>, AppError>;
+    async fn delete(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError>;
 }
@@ -0,0 +1,14 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::one_time_token::{OneTimeTokenEntity, TokenPurpose};
+use async_trait::async_trait;
+
+#[rustfmt::skip]
+#[async_trait]
+pub trait OneTimeTokenRepository: Send + Sync {
+    async fn create(&self, executor: &mut DbExecutor, entity: OneTimeTokenEntity) -> Result<OneTimeTokenEntity, AppError>;
+    async fn find(&self, executor: &mut DbExecutor, token_hash: &str) -> Result<Option<OneTimeTokenEntity>, AppError>;
+    async fn update(&self, executor: &mut DbExecutor, entity: OneTimeTokenEntity) -> Result<Option<OneTimeTokenEntity>, AppError>;
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str, purpose: TokenPurpose) -> Result<u64, AppError>;
+    async fn delete_all_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError>;
+}
@@ -0,0 +1,13 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::recovery_code::RecoveryCodeEntity;
+use async_trait::async_trait;
+
+#[rustfmt::skip]
+#[async_trait]
+pub trait RecoveryCodeRepository: Send + Sync {
+    async fn create(&self, executor: &mut DbExecutor, entity: RecoveryCodeEntity) -> Result<RecoveryCodeEntity, AppError>;
+    async fn find(&self, executor: &mut DbExecutor, code_hash: &str) -> Result<Option<RecoveryCodeEntity>, AppError>;
+    async fn update(&self, executor: &mut DbExecutor, entity: RecoveryCodeEntity) -> Result<Option<RecoveryCodeEntity>, AppError>;
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError>;
+}
@@ -0,0 +1,15 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::refresh_token::RefreshTokenEntity;
+use async_trait::async_trait;
+
+#[rustfmt::skip]
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    async fn create(&self, executor: &mut DbExecutor, entity: RefreshTokenEntity) -> Result<RefreshTokenEntity, AppError>;
+    async fn find(&self, executor: &mut DbExecutor, token_hash: &str) -> Result<Option<RefreshTokenEntity>, AppError>;
+    async fn update(&self, executor: &mut DbExecutor, entity: RefreshTokenEntity) -> Result<Option<RefreshTokenEntity>, AppError>;
+    async fn revoke_family(&self, executor: &mut DbExecutor, family_id: &str) -> Result<u64, AppError>;
+    async fn revoke_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError>;
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError>;
+}
@@ -0,0 +1,18 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::session::SessionEntity;
+use async_trait::async_trait;
+
+#[rustfmt::skip]
+#[async_trait]
+pub trait SessionRepository: Send + Sync {
+    async fn create(&self, executor: &mut DbExecutor, entity: SessionEntity) -> Result<SessionEntity, AppError>;
+    async fn find(&self, executor: &mut DbExecutor, session_id: &str) -> Result<Option<SessionEntity>, AppError>;
+    async fn find_by_jwt_id(&self, executor: &mut DbExecutor, jwt_id: &str) -> Result<Option<SessionEntity>, AppError>;
+    async fn list(&self, executor: &mut DbExecutor, account: &str) -> Result<Vec<SessionEntity>, AppError>;
+    async fn update(&self, executor: &mut DbExecutor, entity: SessionEntity) -> Result<Option<SessionEntity>, AppError>;
+    async fn touch(&self, executor: &mut DbExecutor, session_id: &str) -> Result<u64, AppError>;
+    async fn delete(&self, executor: &mut DbExecutor, session_id: &str) -> Result<u64, AppError>;
+    async fn delete_by_account(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError>;
+    async fn delete_expired(&self, executor: &mut DbExecutor, account: &str, expired_tm: i64) -> Result<u64, AppError>;
+}
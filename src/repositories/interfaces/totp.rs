@@ -0,0 +1,13 @@
+use crate::commons::error::AppError;
+use crate::commons::types::DbExecutor;
+use crate::models::entities::totp::TotpEntity;
+use async_trait::async_trait;
+
+#[rustfmt::skip]
+#[async_trait]
+pub trait TotpRepository: Send + Sync {
+    async fn create(&self, executor: &mut DbExecutor, entity: TotpEntity) -> Result<TotpEntity, AppError>;
+    async fn find(&self, executor: &mut DbExecutor, account: &str) -> Result<Option<TotpEntity>, AppError>;
+    async fn update(&self, executor: &mut DbExecutor, entity: TotpEntity) -> Result<Option<TotpEntity>, AppError>;
+    async fn delete(&self, executor: &mut DbExecutor, account: &str) -> Result<u64, AppError>;
+}
@@ -1,15 +1,18 @@
 pub mod auth;
 pub mod content;
+pub mod member;
 
+use crate::commons::mail::create_mail_sender;
 use crate::commons::types::DbPool;
 use crate::repositories::{Repositories, RepositoriesExt};
-use crate::use_cases::{auth::AuthUseCases, content::ContentUseCases};
+use crate::use_cases::{auth::AuthUseCases, content::ContentUseCases, member::MemberUseCases};
 use std::sync::Arc;
 
 #[derive(Clone)]
 pub struct Modules {
     pub auth: AuthUseCases<Repositories>,
     pub content: ContentUseCases<Repositories>,
+    pub member: MemberUseCases<Repositories>,
 }
 
 pub trait ModulesExt {
@@ -17,6 +20,7 @@ pub trait ModulesExt {
 
     fn auth(&self) -> &AuthUseCases<Self::RepositoriesModule>;
     fn content(&self) -> &ContentUseCases<Self::RepositoriesModule>;
+    fn member(&self) -> &MemberUseCases<Self::RepositoriesModule>;
 }
 
 impl ModulesExt for Modules {
@@ -29,16 +33,25 @@ impl ModulesExt for Modules {
     fn content(&self) -> &ContentUseCases<Self::RepositoriesModule> {
         &self.content
     }
+
+    fn member(&self) -> &MemberUseCases<Self::RepositoriesModule> {
+        &self.member
+    }
 }
 
 impl Modules {
     pub fn new(pool: DbPool) -> Self {
         let repositories = Arc::new(Repositories::new());
 
-        let auth = AuthUseCases::new(pool.clone(), repositories.clone());
-        let content = ContentUseCases::new(pool, repositories);
+        let auth = AuthUseCases::new(pool.clone(), repositories.clone(), create_mail_sender());
+        let content = ContentUseCases::new(pool.clone(), repositories.clone());
+        let member = MemberUseCases::new(pool, repositories, auth.clone());
 
-        Self { auth, content }
+        Self {
+            auth,
+            content,
+            member,
+        }
     }
 }
 
@@ -48,6 +61,7 @@ mod tests {
     use crate::commons::setup;
     use crate::models::dtos::auth::{SigninDto, SignupDto};
     use crate::models::dtos::content::ContentDto;
+    use crate::models::dtos::session::ClientInfoDto;
 
     #[tokio::test]
     async fn test_modules() {
@@ -73,16 +87,19 @@ mod tests {
             password: password.clone(),
         };
 
-        let result = modules.auth().signin(dto).await;
+        let result = modules.auth().signin(dto, ClientInfoDto::default()).await;
         assert!(result.is_ok());
 
-        let token = result.unwrap();
+        let token = result.unwrap().into_token().unwrap().token;
 
-        let result = modules.auth().authenticate(&token).await;
+        let result = modules
+            .auth()
+            .authenticate(&token, ClientInfoDto::default())
+            .await;
         assert!(result.is_ok());
 
-        let dto = result.unwrap();
-        assert_eq!(accunt.clone(), dto.account.clone());
+        let auth_member = result.unwrap();
+        assert_eq!(accunt.clone(), auth_member.account.clone());
 
         let dto = ContentDto {
             content_id: 0,
@@ -92,7 +109,7 @@ mod tests {
             body: "body".to_string(),
         };
 
-        let result = modules.content().post(dto.clone()).await;
+        let result = modules.content().post(&auth_member, dto.clone()).await;
         assert!(result.is_ok());
 
         let dto = result.unwrap();
@@ -100,7 +117,7 @@ mod tests {
         assert_eq!(dto.title.clone(), "title".to_string());
         assert_eq!(dto.body.clone(), "body".to_string());
 
-        let result = modules.content().get(dto.content_id.clone()).await;
+        let result = modules.content().get(dto.content_id).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -110,14 +127,14 @@ mod tests {
         dto.title = "title2".to_string();
         dto.body = "body2".to_string();
 
-        let result = modules.content().edit(dto.clone()).await;
+        let result = modules.content().edit(&auth_member, dto.clone()).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
         assert_eq!(result.title.clone(), "title2".to_string());
         assert_eq!(result.body.clone(), "body2".to_string());
 
-        let result = modules.content().remove(dto.content_id.clone()).await;
+        let result = modules.content().remove(&auth_member, dto.content_id).await;
         assert!(result.is_ok());
 
         let result = result.unwrap();
@@ -1,255 +1,2677 @@
-use crate::commons::config::JWT_EXPIRATION_SECONDS;
-use crate::commons::types::{BoxError, DbPool};
-use crate::models::dtos::auth::{SigninDto, SignupDto};
+use crate::commons::config::{
+    EMAIL_VERIFICATION, EMAIL_VERIFICATION_EXPIRATION_SECONDS, EmailVerificationPolicy,
+    JWT_EXPIRATION_SECONDS, PASSWORD_RESET_EXPIRATION_SECONDS, RECOVERY_CODE_COUNT,
+    REFRESH_TOKEN_EXPIRATION_SECONDS, REGISTRATION_MODE, SECOND_FACTOR_EXPIRATION_SECONDS,
+};
+use crate::commons::crypto::{generate_token, hash_token};
+use crate::commons::error::AppError;
+use crate::commons::jwt;
+use crate::commons::mail::{Mail, MailSender};
+use crate::commons::password;
+use crate::commons::totp;
+use crate::commons::types::{DbExecutor, DbPool};
+use crate::commons::validation::FieldError;
+use crate::models::dtos::access_token::{AccessTokenCreateDto, AccessTokenDto};
+use crate::models::dtos::auth::{
+    EmailVerificationConfirmDto, EmailVerificationRequestDto, PasswordDto, PasswordResetConfirmDto,
+    PasswordResetRequestDto, RecoveryCodesDto, RefreshDto, SecondFactorChallengeDto,
+    SecondFactorDto, SigninDto, SigninResultDto, SignupDto, TokenDto, TotpCodeDto, TotpDisableDto,
+    TotpEnrollmentDto,
+};
+use crate::models::dtos::auth_event::{AuthEventDto, AuthEventPageDto, AuthEventQueryDto};
 use crate::models::dtos::member::AuthMemberDto;
-use crate::models::entities::auth::AuthEntity;
+use crate::models::dtos::session::{ClientInfoDto, SessionDto};
+use crate::models::entities::{
+    access_token::{ACCESS_TOKEN_PREFIX, AccessTokenEntity},
+    auth::AuthEntity,
+    auth_event::{AuthEventEntity, AuthEventType},
+    member::{MemberStatus, Role},
+    one_time_token::{OneTimeTokenEntity, TokenPurpose},
+    recovery_code::RecoveryCodeEntity,
+    refresh_token::RefreshTokenEntity,
+    session::SessionEntity,
+    totp::TotpEntity,
+};
 use crate::repositories::RepositoriesExt;
-use crate::repositories::interfaces::{auth::AuthRepository, member::MemberRepository};
+use crate::repositories::interfaces::{
+    access_token::AccessTokenRepository, auth::AuthRepository, auth_event::AuthEventRepository,
+    member::MemberRepository, one_time_token::OneTimeTokenRepository,
+    recovery_code::RecoveryCodeRepository, refresh_token::RefreshTokenRepository,
+    session::SessionRepository, totp::TotpRepository,
+};
 use derive_new::new;
 use std::sync::Arc;
 
+const SESSION_TOUCH_SECONDS: i64 = 60;
+
 #[derive(new, Clone)]
 pub struct AuthUseCases<R: RepositoriesExt> {
     pool: DbPool,
     repositories: Arc<R>,
+    mailer: Arc<dyn MailSender>,
 }
 
 impl<R: RepositoriesExt> AuthUseCases<R> {
-    pub async fn signup(&self, dto: SignupDto) -> Result<(), BoxError> {
+    pub async fn signup(&self, dto: SignupDto) -> Result<MemberStatus, AppError> {
         if dto.password.clone() != dto.confirm_password.clone() {
-            return Err("password does not match".into());
+            return Err(AppError::Validation(vec![FieldError::new(
+                "confirmPassword",
+                "password does not match",
+            )]));
+        }
+
+        if *EMAIL_VERIFICATION != EmailVerificationPolicy::Optional && dto.email.is_none() {
+            return Err(AppError::Validation(vec![FieldError::new(
+                "email",
+                "must not be empty",
+            )]));
         }
 
-        let password = async_argon2::hash(dto.password.clone()).await?;
+        let password = password::hash(dto.password.clone()).await?;
         let mut entity = dto.to_entity();
         entity.password = password;
 
         let mut executor = self.pool.begin().await?;
 
-        self.repositories
+        let member = self
+            .repositories
             .member_repository()
-            .create(&mut *executor, entity)
+            .create(&mut executor, entity)
             .await?;
 
+        // counted after the insert so concurrent signups cannot both see an empty table
+        let first_member = self
+            .repositories
+            .member_repository()
+            .count(&mut executor)
+            .await?
+            == 1;
+
+        let status = MemberStatus::on_signup(*REGISTRATION_MODE, first_member);
+        if status != member.status {
+            self.repositories
+                .member_repository()
+                .update_status(&mut executor, &member.account, status)
+                .await?;
+        }
+
+        if first_member {
+            self.repositories
+                .member_repository()
+                .update_role(&mut executor, &member.account, Role::Admin)
+                .await?;
+        }
+
+        let verification = match &member.email {
+            Some(email) => Some((
+                email.clone(),
+                self.issue_email_verification(&mut executor, &member.account)
+                    .await?,
+            )),
+            None => None,
+        };
+
         executor.commit().await?;
 
-        Ok(())
+        if let Some((email, token)) = verification {
+            self.send_email_verification(&member.account, &email, &token)
+                .await;
+        }
+
+        Ok(status)
     }
 
-    pub async fn signin(&self, dto: SigninDto) -> Result<String, BoxError> {
+    pub async fn signin(
+        &self,
+        dto: SigninDto,
+        client: ClientInfoDto,
+    ) -> Result<SigninResultDto, AppError> {
         let mut executor = self.pool.acquire().await?;
 
-        let member = self
+        // unknown accounts pay for a hash check too and get the same error as a wrong password
+        let Some(member) = self
             .repositories
             .member_repository()
-            .find(&mut *executor, &dto.account)
+            .find(&mut executor, &dto.account)
             .await?
-            .ok_or(sqlx::Error::RowNotFound)?;
+        else {
+            password::verify_dummy(dto.password).await?;
+            self.record_event(
+                &mut executor,
+                &dto.account,
+                AuthEventType::SigninFailed,
+                &client,
+            )
+            .await?;
+            return Err(Self::invalid_credentials());
+        };
 
         let auth = self
             .repositories
             .auth_repository()
-            .find(&mut *executor, &dto.account)
+            .find(&mut executor, &dto.account)
             .await?;
 
-        if let Some(auth) = auth.clone() {
-            if auth.is_locked() {
-                return Err("account is locked".into());
-            }
+        let mut executor = self.pool.begin().await?;
+
+        let verified = password::verify(dto.password.clone(), member.password.clone()).await?;
+
+        // the lock is only revealed to someone who knows the password
+        if auth.as_ref().is_some_and(|auth| auth.is_locked()) {
+            self.record_event(
+                &mut executor,
+                &dto.account,
+                AuthEventType::SigninLocked,
+                &client,
+            )
+            .await?;
+            executor.commit().await?;
+
+            return match verified {
+                true => Err(AppError::Locked("account is locked".to_string())),
+                false => Err(Self::invalid_credentials()),
+            };
+        }
+
+        if !verified {
+            self.record_event(
+                &mut executor,
+                &dto.account,
+                AuthEventType::SigninFailed,
+                &client,
+            )
+            .await?;
+            self.record_missmatch(&mut executor, &dto.account, auth, &client)
+                .await?;
+            executor.commit().await?;
+
+            return Err(Self::invalid_credentials());
+        }
+
+        Self::check_status(member.status)?;
+
+        if !EMAIL_VERIFICATION.allows_signin(member.email_verified_at.is_some()) {
+            return Err(AppError::Forbidden(
+                "email address is not verified".to_string(),
+            ));
+        }
+
+        // hashes made with older argon2 parameters are upgraded while the password is at hand
+        if password::needs_rehash(&member.password) {
+            self.repositories
+                .member_repository()
+                .update_password(
+                    &mut executor,
+                    &dto.account,
+                    &password::hash(dto.password).await?,
+                )
+                .await?;
+        }
+
+        let totp = self
+            .repositories
+            .totp_repository()
+            .find(&mut executor, &dto.account)
+            .await?
+            .filter(|totp| totp.is_confirmed());
+
+        if totp.is_some() {
+            let challenge_token = self
+                .issue_one_time_token(
+                    &mut executor,
+                    &dto.account,
+                    TokenPurpose::SecondFactor,
+                    *SECOND_FACTOR_EXPIRATION_SECONDS,
+                )
+                .await?;
+            executor.commit().await?;
+
+            return Ok(SigninResultDto::SecondFactor(
+                SecondFactorChallengeDto::new(&challenge_token, *SECOND_FACTOR_EXPIRATION_SECONDS),
+            ));
         }
 
+        let token = self
+            .issue_session(&mut executor, &dto.account, auth, client)
+            .await?;
+        executor.commit().await?;
+
+        Ok(SigninResultDto::Token(token))
+    }
+
+    pub async fn verify_second_factor(
+        &self,
+        dto: SecondFactorDto,
+        client: ClientInfoDto,
+    ) -> Result<TokenDto, AppError> {
         let mut executor = self.pool.begin().await?;
 
-        if !async_argon2::verify(dto.password, member.password).await? {
-            match auth {
-                Some(mut auth) => {
-                    auth.missmatched();
+        let mut challenge = self
+            .repositories
+            .one_time_token_repository()
+            .find(&mut executor, &hash_token(&dto.challenge_token))
+            .await?
+            .filter(|token| token.is_valid(TokenPurpose::SecondFactor))
+            .ok_or(AppError::Unauthorized(
+                "invalid or expired challenge".to_string(),
+            ))?;
+        let account = challenge.account.clone();
+
+        let auth = self
+            .repositories
+            .auth_repository()
+            .find(&mut executor, &account)
+            .await?;
+
+        if auth.as_ref().is_some_and(|auth| auth.is_locked()) {
+            self.record_event(
+                &mut executor,
+                &account,
+                AuthEventType::SigninLocked,
+                &client,
+            )
+            .await?;
+            executor.commit().await?;
+
+            return Err(AppError::Locked("account is locked".to_string()));
+        }
+
+        let member = self
+            .repositories
+            .member_repository()
+            .find(&mut executor, &account)
+            .await?
+            .ok_or(AppError::Unauthorized(
+                "invalid or expired challenge".to_string(),
+            ))?;
+
+        Self::check_status(member.status)?;
+
+        let mut totp = self
+            .repositories
+            .totp_repository()
+            .find(&mut executor, &account)
+            .await?
+            .filter(|totp| totp.is_confirmed())
+            .ok_or(AppError::Unauthorized(
+                "invalid or expired challenge".to_string(),
+            ))?;
+
+        let verified = match totp::is_code(&dto.code) {
+            true => match totp::verify(&totp.secret, &dto.code, totp.last_step)? {
+                Some(step) => {
+                    totp.accept(step);
                     self.repositories
-                        .auth_repository()
-                        .update(&mut *executor, auth)
+                        .totp_repository()
+                        .update(&mut executor, totp)
                         .await?;
+                    true
                 }
-                None => {
-                    self.repositories
-                        .auth_repository()
-                        .create(&mut *executor, AuthEntity::new_missmatched(dto.account))
-                        .await?;
+                None => false,
+            },
+            false => {
+                let code = self
+                    .repositories
+                    .recovery_code_repository()
+                    .find(&mut executor, &hash_token(&dto.code.to_lowercase()))
+                    .await?
+                    .filter(|code| code.is_valid(&account));
+                match code {
+                    Some(mut code) => {
+                        code.consume();
+                        self.repositories
+                            .recovery_code_repository()
+                            .update(&mut executor, code)
+                            .await?;
+                        true
+                    }
+                    None => false,
                 }
             }
+        };
+
+        if !verified {
+            self.record_event(
+                &mut executor,
+                &account,
+                AuthEventType::SecondFactorFailed,
+                &client,
+            )
+            .await?;
+            self.record_missmatch(&mut executor, &account, auth, &client)
+                .await?;
             executor.commit().await?;
 
-            return Err("password does not match".into());
+            return Err(AppError::Unauthorized("invalid code".to_string()));
         }
 
-        let claims = simple_jwt::Claims::new(&dto.account, *JWT_EXPIRATION_SECONDS);
-        let token = simple_jwt::encode(&claims.clone())?;
+        challenge.consume();
+        self.repositories
+            .one_time_token_repository()
+            .update(&mut executor, challenge)
+            .await?;
 
-        match auth {
-            Some(mut auth) => {
-                auth.signin(claims.clone());
-                self.repositories
-                    .auth_repository()
-                    .update(&mut *executor, auth)
-                    .await?;
-            }
-            None => {
-                self.repositories
-                    .auth_repository()
-                    .create(&mut *executor, AuthEntity::new_signin(claims))
-                    .await?;
-            }
-        }
+        let token = self
+            .issue_session(&mut executor, &account, auth, client)
+            .await?;
         executor.commit().await?;
 
         Ok(token)
     }
 
-    pub async fn authenticate(&self, token: &str) -> Result<AuthMemberDto, BoxError> {
-        let claims = simple_jwt::decode(token)?;
+    pub async fn enroll_totp(
+        &self,
+        auth_member: &AuthMemberDto,
+    ) -> Result<TotpEnrollmentDto, AppError> {
+        let mut executor = self.pool.begin().await?;
 
-        let mut executor = self.pool.acquire().await?;
+        let totp = self
+            .repositories
+            .totp_repository()
+            .find(&mut executor, &auth_member.account)
+            .await?;
 
-        let auth = self
+        if let Some(totp) = totp {
+            if totp.is_confirmed() {
+                return Err(AppError::Conflict(
+                    "two-factor authentication is already enabled".to_string(),
+                ));
+            }
+            self.repositories
+                .totp_repository()
+                .delete(&mut executor, &auth_member.account)
+                .await?;
+        }
+
+        let secret = totp::generate_secret()?;
+        self.repositories
+            .totp_repository()
+            .create(
+                &mut executor,
+                TotpEntity::new(&auth_member.account, &secret),
+            )
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(TotpEnrollmentDto {
+            otpauth_uri: totp::otpauth_uri(&secret, &auth_member.account)?,
+            secret,
+        })
+    }
+
+    pub async fn confirm_totp(
+        &self,
+        auth_member: &AuthMemberDto,
+        dto: TotpCodeDto,
+    ) -> Result<RecoveryCodesDto, AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let mut totp = self
             .repositories
-            .auth_repository()
-            .find(&mut *executor, &claims.sub)
+            .totp_repository()
+            .find(&mut executor, &auth_member.account)
             .await?
-            .ok_or(sqlx::Error::RowNotFound)?;
+            .filter(|totp| !totp.is_confirmed())
+            .ok_or(AppError::NotFound(
+                "two-factor enrollment not found".to_string(),
+            ))?;
+
+        let step = totp::verify(&totp.secret, &dto.code, totp.last_step)?.ok_or(
+            AppError::Validation(vec![FieldError::new("code", "code does not match")]),
+        )?;
+
+        totp.accept(step);
+        self.repositories
+            .totp_repository()
+            .update(&mut executor, totp)
+            .await?;
+
+        self.repositories
+            .recovery_code_repository()
+            .delete_by_account(&mut executor, &auth_member.account)
+            .await?;
 
-        if !auth.is_signin(claims.clone()) {
-            return Err("invalid token".into());
+        let mut recovery_codes = Vec::with_capacity(*RECOVERY_CODE_COUNT);
+        for _ in 0..*RECOVERY_CODE_COUNT {
+            let code = generate_token(5)?;
+            self.repositories
+                .recovery_code_repository()
+                .create(
+                    &mut executor,
+                    RecoveryCodeEntity::new(&auth_member.account, &hash_token(&code)),
+                )
+                .await?;
+            recovery_codes.push(code);
         }
 
-        let member = self
+        executor.commit().await?;
+
+        Ok(RecoveryCodesDto { recovery_codes })
+    }
+
+    pub async fn disable_totp(
+        &self,
+        auth_member: &AuthMemberDto,
+        dto: TotpDisableDto,
+        client: ClientInfoDto,
+    ) -> Result<(), AppError> {
+        self.reauthenticate(&auth_member.account, dto.password, "password", &client)
+            .await?;
+
+        let mut executor = self.pool.begin().await?;
+
+        let count = self
             .repositories
-            .member_repository()
-            .find(&mut *executor, &claims.sub)
-            .await?
-            .ok_or(sqlx::Error::RowNotFound)?;
+            .totp_repository()
+            .delete(&mut executor, &auth_member.account)
+            .await?;
+        if count == 0 {
+            return Err(AppError::NotFound(
+                "two-factor authentication is not enabled".to_string(),
+            ));
+        }
+
+        self.repositories
+            .recovery_code_repository()
+            .delete_by_account(&mut executor, &auth_member.account)
+            .await?;
+
+        executor.commit().await?;
 
-        Ok(AuthMemberDto::from_entity(member, auth))
+        Ok(())
     }
 
-    pub async fn signout(&self, account: &str) -> Result<(), BoxError> {
+    // confirming the password on a signed in session counts toward the lockout like a failed signin
+    pub async fn reauthenticate(
+        &self,
+        account: &str,
+        password: String,
+        field: &str,
+        client: &ClientInfoDto,
+    ) -> Result<(), AppError> {
         let mut executor = self.pool.begin().await?;
 
-        let mut entity = match self
+        let member = self
             .repositories
-            .auth_repository()
-            .find(&mut *executor, &account)
+            .member_repository()
+            .find(&mut executor, account)
             .await?
-        {
-            Some(entity) => entity,
-            None => return Ok(()),
-        };
-
-        entity.signout();
+            .ok_or(AppError::NotFound("member not found".to_string()))?;
 
-        let entity = self
+        let auth = self
             .repositories
             .auth_repository()
-            .update(&mut *executor, entity)
+            .find(&mut executor, account)
             .await?;
 
-        if entity.is_some() {
+        if auth.as_ref().is_some_and(|auth| auth.is_locked()) {
+            return Err(AppError::Locked("account is locked".to_string()));
+        }
+
+        if !password::verify(password, member.password).await? {
+            self.record_event(&mut executor, account, AuthEventType::ReauthFailed, client)
+                .await?;
+            self.record_missmatch(&mut executor, account, auth, client)
+                .await?;
+            executor.commit().await?;
+
+            return Err(AppError::Validation(vec![FieldError::new(
+                field,
+                "password does not match",
+            )]));
+        }
+
+        if let Some(mut auth) = auth.filter(|auth| auth.missmatch > 0) {
+            auth.unlock();
+            self.repositories
+                .auth_repository()
+                .update(&mut executor, auth)
+                .await?;
             executor.commit().await?;
         }
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::commons::setup;
-    use crate::models::dtos::auth::{SigninDto, SignupDto};
-    use crate::repositories::Repositories;
-    use crate::use_cases::auth::AuthUseCases;
+    async fn record_missmatch(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+        auth: Option<AuthEntity>,
+        client: &ClientInfoDto,
+    ) -> Result<(), AppError> {
+        let auth = match auth {
+            Some(mut auth) => {
+                auth.missmatched();
+                self.repositories
+                    .auth_repository()
+                    .update(executor, auth.clone())
+                    .await?;
+                auth
+            }
+            None => {
+                self.repositories
+                    .auth_repository()
+                    .create(executor, AuthEntity::new_missmatched(account.to_string()))
+                    .await?
+            }
+        };
 
-    use std::sync::Arc;
+        if auth.is_locked() {
+            self.record_event(executor, account, AuthEventType::AccountLocked, client)
+                .await?;
+        }
+        Ok(())
+    }
 
-    #[tokio::test]
-    async fn test_auth_use_cases() {
-        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+    async fn record_event(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+        event_type: AuthEventType,
+        client: &ClientInfoDto,
+    ) -> Result<(), AppError> {
+        self.repositories
+            .auth_event_repository()
+            .create(
+                executor,
+                AuthEventEntity::new(
+                    account,
+                    event_type,
+                    client.ip_address.clone(),
+                    client.user_agent.clone(),
+                ),
+            )
+            .await?;
+        Ok(())
+    }
 
-        let repositories = Repositories::new();
-        let use_cases = AuthUseCases::new(pool.clone(), Arc::new(repositories));
+    async fn issue_session(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+        auth: Option<AuthEntity>,
+        client: ClientInfoDto,
+    ) -> Result<TokenDto, AppError> {
+        let claims = jwt::Claims::new(account, *JWT_EXPIRATION_SECONDS);
+        let token = jwt::encode(&claims)?;
 
-        let account = "account".to_string();
-        let password = "password".to_string();
+        self.record_event(executor, account, AuthEventType::Signin, &client)
+            .await?;
 
-        let signup_dto = SignupDto {
-            account: account.clone(),
-            password: password.clone(),
-            confirm_password: password.clone(),
-            name: None,
-            email: None,
-        };
+        self.repositories
+            .session_repository()
+            .delete_expired(
+                executor,
+                account,
+                claims.iat - *REFRESH_TOKEN_EXPIRATION_SECONDS,
+            )
+            .await?;
 
-        let result = use_cases.signup(signup_dto.clone()).await;
-        assert!(result.is_ok());
+        let session = self
+            .repositories
+            .session_repository()
+            .create(
+                executor,
+                SessionEntity::new(
+                    &generate_token(16)?,
+                    claims.clone(),
+                    client.user_agent,
+                    client.ip_address,
+                ),
+            )
+            .await?;
 
-        let signin_dto = SigninDto {
-            account: account.clone(),
-            password: password.clone(),
-        };
+        let refresh_token = self
+            .issue_refresh_token(executor, account, &session.session_id)
+            .await?;
 
-        let result = use_cases.signin(signin_dto.clone()).await;
-        assert!(result.is_ok());
+        match auth {
+            Some(mut auth) => {
+                auth.signin(claims.clone());
+                self.repositories
+                    .auth_repository()
+                    .update(executor, auth)
+                    .await?;
+            }
+            None => {
+                self.repositories
+                    .auth_repository()
+                    .create(executor, AuthEntity::new_signin(claims))
+                    .await?;
+            }
+        }
 
-        let token = result.unwrap();
+        Ok(TokenDto {
+            token,
+            refresh_token,
+        })
+    }
 
-        let result = use_cases.authenticate(&token).await;
-        assert!(result.is_ok());
+    pub async fn refresh(
+        &self,
+        dto: RefreshDto,
+        client: ClientInfoDto,
+    ) -> Result<TokenDto, AppError> {
+        let mut executor = self.pool.begin().await?;
 
-        let auth_member_dto = result.unwrap();
+        let entity = self
+            .repositories
+            .refresh_token_repository()
+            .find(&mut executor, &hash_token(&dto.refresh_token))
+            .await?
+            .ok_or(AppError::Unauthorized("invalid refresh token".to_string()))?;
+        let account = entity.account.clone();
 
-        assert_eq!(auth_member_dto.account, account.clone());
+        match self.rotate_refresh_token(&mut executor, entity).await {
+            Ok(token) => {
+                executor.commit().await?;
+                Ok(token)
+            }
+            // rejections are committed too, a reused token has already revoked its session
+            Err(AppError::Unauthorized(message)) => {
+                self.record_event(
+                    &mut executor,
+                    &account,
+                    AuthEventType::RefreshRejected,
+                    &client,
+                )
+                .await?;
+                executor.commit().await?;
+                Err(AppError::Unauthorized(message))
+            }
+            Err(e) => Err(e),
+        }
     }
 
-    #[tokio::test]
-    async fn test_auth_use_cases_pwd_lock() {
-        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+    async fn rotate_refresh_token(
+        &self,
+        executor: &mut DbExecutor,
+        mut entity: RefreshTokenEntity,
+    ) -> Result<TokenDto, AppError> {
+        if entity.revoked_at.is_some() || entity.is_expired() {
+            return Err(AppError::Unauthorized("invalid refresh token".to_string()));
+        }
 
-        let repositories = Repositories::new();
-        let use_cases = AuthUseCases::new(pool.clone(), Arc::new(repositories));
+        if entity.rotated_at.is_some() {
+            self.revoke_session(executor, &entity.family_id).await?;
 
-        let account = "account".to_string();
-        let password = "password".to_string();
+            tracing::warn!("refresh token reuse detected: {}", entity.account);
+            return Err(AppError::Unauthorized("invalid refresh token".to_string()));
+        }
 
-        let signup_dto = SignupDto {
-            account: account.clone(),
-            password: password.clone(),
-            confirm_password: password.clone(),
-            name: None,
-            email: None,
-        };
+        let mut session = self
+            .repositories
+            .session_repository()
+            .find(&mut *executor, &entity.family_id)
+            .await?
+            .ok_or(AppError::Unauthorized("invalid refresh token".to_string()))?;
 
-        let result = use_cases.signup(signup_dto.clone()).await;
-        assert!(result.is_ok());
+        let auth = self
+            .repositories
+            .auth_repository()
+            .find(&mut *executor, &entity.account)
+            .await?
+            .ok_or(AppError::Unauthorized("invalid refresh token".to_string()))?;
 
-        let miss_password = "missmatch".to_string();
+        if auth.is_locked() {
+            return Err(AppError::Unauthorized("invalid refresh token".to_string()));
+        }
 
-        let signin_dto = SigninDto {
-            account: account.clone(),
-            password: miss_password.clone(),
+        self.repositories
+            .member_repository()
+            .find(&mut *executor, &entity.account)
+            .await?
+            .filter(|member| member.is_active())
+            .ok_or(AppError::Unauthorized("invalid refresh token".to_string()))?;
+
+        entity.rotate();
+        self.repositories
+            .refresh_token_repository()
+            .update(&mut *executor, entity.clone())
+            .await?;
+
+        let refresh_token = self
+            .issue_refresh_token(executor, &entity.account, &entity.family_id)
+            .await?;
+
+        let claims = jwt::Claims::new(&entity.account, *JWT_EXPIRATION_SECONDS);
+        let token = jwt::encode(&claims)?;
+
+        session.refreshed(claims);
+        self.repositories
+            .session_repository()
+            .update(&mut *executor, session)
+            .await?;
+
+        Ok(TokenDto {
+            token,
+            refresh_token,
+        })
+    }
+
+    fn invalid_credentials() -> AppError {
+        AppError::Unauthorized("invalid credentials".to_string())
+    }
+
+    fn check_status(status: MemberStatus) -> Result<(), AppError> {
+        match status {
+            MemberStatus::Active => Ok(()),
+            MemberStatus::Pending => Err(AppError::Forbidden(
+                "account is pending approval".to_string(),
+            )),
+            MemberStatus::Suspended => Err(AppError::Forbidden("account is suspended".to_string())),
+        }
+    }
+
+    async fn issue_refresh_token(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+        family_id: &str,
+    ) -> Result<String, AppError> {
+        let refresh_token = generate_token(32)?;
+
+        self.repositories
+            .refresh_token_repository()
+            .create(
+                executor,
+                RefreshTokenEntity::new(account, family_id, &hash_token(&refresh_token)),
+            )
+            .await?;
+
+        Ok(refresh_token)
+    }
+
+    async fn revoke_session(
+        &self,
+        executor: &mut DbExecutor,
+        session_id: &str,
+    ) -> Result<u64, AppError> {
+        self.repositories
+            .refresh_token_repository()
+            .revoke_family(executor, session_id)
+            .await?;
+
+        self.repositories
+            .session_repository()
+            .delete(executor, session_id)
+            .await
+    }
+
+    pub async fn authenticate(
+        &self,
+        token: &str,
+        client: ClientInfoDto,
+    ) -> Result<AuthMemberDto, AppError> {
+        let claims = jwt::decode(token)?;
+
+        let mut executor = self.pool.acquire().await?;
+
+        // only well signed tokens are recorded, so garbage bearer headers cannot flood the log
+        let result = self.authenticate_claims(&mut executor, &claims).await;
+        if let Err(AppError::Unauthorized(_)) = &result {
+            self.record_event(
+                &mut executor,
+                &claims.sub,
+                AuthEventType::TokenRejected,
+                &client,
+            )
+            .await?;
+        }
+        result
+    }
+
+    async fn authenticate_claims(
+        &self,
+        executor: &mut DbExecutor,
+        claims: &jwt::Claims,
+    ) -> Result<AuthMemberDto, AppError> {
+        let session = self
+            .repositories
+            .session_repository()
+            .find_by_jwt_id(&mut *executor, &claims.jti)
+            .await?
+            .ok_or(AppError::Unauthorized("invalid token".to_string()))?;
+
+        if !session.is_signin(claims) {
+            return Err(AppError::Unauthorized("invalid token".to_string()));
+        }
+
+        if session.is_stale(SESSION_TOUCH_SECONDS) {
+            self.repositories
+                .session_repository()
+                .touch(&mut *executor, &session.session_id)
+                .await?;
+        }
+
+        let auth = self
+            .repositories
+            .auth_repository()
+            .find(&mut *executor, &claims.sub)
+            .await?
+            .ok_or(AppError::Unauthorized("invalid token".to_string()))?;
+
+        let member = self
+            .repositories
+            .member_repository()
+            .find(&mut *executor, &claims.sub)
+            .await?
+            .filter(|member| member.is_active())
+            .ok_or(AppError::Unauthorized("invalid token".to_string()))?;
+
+        Ok(AuthMemberDto::from_entity(member, auth, session))
+    }
+
+    pub async fn signout(
+        &self,
+        auth_member: &AuthMemberDto,
+        client: ClientInfoDto,
+    ) -> Result<(), AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        self.revoke_session(&mut executor, &auth_member.session_id)
+            .await?;
+
+        self.record_event(
+            &mut executor,
+            &auth_member.account,
+            AuthEventType::Signout,
+            &client,
+        )
+        .await?;
+
+        executor.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn signout_all(&self, account: &str, client: ClientInfoDto) -> Result<u64, AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let count = self.revoke_account_sessions(&mut executor, account).await?;
+
+        self.record_event(&mut executor, account, AuthEventType::SignoutAll, &client)
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(count)
+    }
+
+    async fn revoke_account_sessions(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+    ) -> Result<u64, AppError> {
+        self.repositories
+            .refresh_token_repository()
+            .revoke_account(executor, account)
+            .await?;
+
+        self.repositories
+            .session_repository()
+            .delete_by_account(executor, account)
+            .await
+    }
+
+    pub async fn change_password(
+        &self,
+        auth_member: &AuthMemberDto,
+        dto: PasswordDto,
+        client: ClientInfoDto,
+    ) -> Result<(), AppError> {
+        self.reauthenticate(
+            &auth_member.account,
+            dto.current_password,
+            "currentPassword",
+            &client,
+        )
+        .await?;
+
+        let mut executor = self.pool.begin().await?;
+
+        let mut member = self
+            .repositories
+            .member_repository()
+            .find(&mut executor, &auth_member.account)
+            .await?
+            .ok_or(AppError::NotFound("member not found".to_string()))?;
+
+        member.password = password::hash(dto.password).await?;
+
+        self.repositories
+            .member_repository()
+            .update(&mut executor, member)
+            .await?;
+
+        let sessions = self
+            .repositories
+            .session_repository()
+            .list(&mut executor, &auth_member.account)
+            .await?;
+
+        for session in sessions {
+            if session.session_id != auth_member.session_id {
+                self.revoke_session(&mut executor, &session.session_id)
+                    .await?;
+            }
+        }
+
+        executor.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn request_email_verification(
+        &self,
+        dto: EmailVerificationRequestDto,
+    ) -> Result<(), AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let email = self
+            .repositories
+            .member_repository()
+            .find(&mut executor, &dto.account)
+            .await?
+            .filter(|member| member.email_verified_at.is_none())
+            .and_then(|member| member.email);
+
+        let Some(email) = email else {
+            return Ok(());
+        };
+
+        let token = self
+            .issue_email_verification(&mut executor, &dto.account)
+            .await?;
+
+        executor.commit().await?;
+
+        self.send_email_verification(&dto.account, &email, &token)
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn confirm_email_verification(
+        &self,
+        dto: EmailVerificationConfirmDto,
+    ) -> Result<(), AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let mut token = self
+            .repositories
+            .one_time_token_repository()
+            .find(&mut executor, &hash_token(&dto.token))
+            .await?
+            .filter(|token| token.is_valid(TokenPurpose::EmailVerification))
+            .ok_or(AppError::Unauthorized(
+                "invalid or expired token".to_string(),
+            ))?;
+
+        let mut member = self
+            .repositories
+            .member_repository()
+            .find(&mut executor, &token.account)
+            .await?
+            .ok_or(AppError::Unauthorized(
+                "invalid or expired token".to_string(),
+            ))?;
+
+        member.verify_email();
+        self.repositories
+            .member_repository()
+            .update(&mut executor, member)
+            .await?;
+
+        token.consume();
+        self.repositories
+            .one_time_token_repository()
+            .update(&mut executor, token)
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(())
+    }
+
+    async fn issue_one_time_token(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+        purpose: TokenPurpose,
+        seconds: i64,
+    ) -> Result<String, AppError> {
+        self.repositories
+            .one_time_token_repository()
+            .delete_by_account(executor, account, purpose)
+            .await?;
+
+        let token = generate_token(32)?;
+        self.repositories
+            .one_time_token_repository()
+            .create(
+                executor,
+                OneTimeTokenEntity::new(account, purpose, &hash_token(&token), seconds),
+            )
+            .await?;
+
+        Ok(token)
+    }
+
+    // issued inside the caller's transaction, the mail is sent after it has committed
+    pub async fn issue_email_verification(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+    ) -> Result<String, AppError> {
+        self.issue_one_time_token(
+            executor,
+            account,
+            TokenPurpose::EmailVerification,
+            *EMAIL_VERIFICATION_EXPIRATION_SECONDS,
+        )
+        .await
+    }
+
+    pub async fn send_email_verification(&self, account: &str, email: &str, token: &str) {
+        let body = format!(
+            "Use the following token to verify the email address of {}.\n\n{}\n\nThe token expires in {} hours.",
+            account,
+            token,
+            *EMAIL_VERIFICATION_EXPIRATION_SECONDS / 3600
+        );
+        self.send_mail(Mail::new(email, "Email verification", &body))
+            .await;
+    }
+
+    // mail failures are logged only, so responses do not reveal whether an account exists
+    async fn send_mail(&self, mail: Mail) {
+        if let Err(e) = self.mailer.send(mail).await {
+            tracing::error!("failed to send mail: {}", e);
+        }
+    }
+
+    pub async fn request_password_reset(
+        &self,
+        dto: PasswordResetRequestDto,
+    ) -> Result<(), AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let email = self
+            .repositories
+            .member_repository()
+            .find(&mut executor, &dto.account)
+            .await?
+            .and_then(|member| member.email);
+
+        // unknown accounts and accounts without an email get the same response
+        let Some(email) = email else {
+            return Ok(());
+        };
+
+        let token = self
+            .issue_one_time_token(
+                &mut executor,
+                &dto.account,
+                TokenPurpose::PasswordReset,
+                *PASSWORD_RESET_EXPIRATION_SECONDS,
+            )
+            .await?;
+
+        executor.commit().await?;
+
+        let body = format!(
+            "Use the following token to reset the password of {}.\n\n{}\n\nThe token expires in {} minutes.",
+            dto.account,
+            token,
+            *PASSWORD_RESET_EXPIRATION_SECONDS / 60
+        );
+        self.send_mail(Mail::new(&email, "Password reset", &body))
+            .await;
+
+        Ok(())
+    }
+
+    pub async fn confirm_password_reset(
+        &self,
+        dto: PasswordResetConfirmDto,
+    ) -> Result<(), AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let mut token = self
+            .repositories
+            .one_time_token_repository()
+            .find(&mut executor, &hash_token(&dto.token))
+            .await?
+            .filter(|token| token.is_valid(TokenPurpose::PasswordReset))
+            .ok_or(AppError::Unauthorized(
+                "invalid or expired token".to_string(),
+            ))?;
+
+        let mut member = self
+            .repositories
+            .member_repository()
+            .find(&mut executor, &token.account)
+            .await?
+            .ok_or(AppError::Unauthorized(
+                "invalid or expired token".to_string(),
+            ))?;
+
+        member.password = password::hash(dto.password).await?;
+
+        self.repositories
+            .member_repository()
+            .update(&mut executor, member)
+            .await?;
+
+        token.consume();
+        self.repositories
+            .one_time_token_repository()
+            .update(&mut executor, token.clone())
+            .await?;
+
+        if let Some(mut auth) = self
+            .repositories
+            .auth_repository()
+            .find(&mut executor, &token.account)
+            .await?
+        {
+            auth.unlock();
+            self.repositories
+                .auth_repository()
+                .update(&mut executor, auth)
+                .await?;
+        }
+
+        self.revoke_account_sessions(&mut executor, &token.account)
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn sessions(&self, auth_member: &AuthMemberDto) -> Result<Vec<SessionDto>, AppError> {
+        let mut executor = self.pool.acquire().await?;
+
+        let sessions = self
+            .repositories
+            .session_repository()
+            .list(&mut executor, &auth_member.account)
+            .await?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| SessionDto::from_entity(session, &auth_member.session_id))
+            .collect())
+    }
+
+    pub async fn remove_session(&self, account: &str, session_id: &str) -> Result<(), AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let session = self
+            .repositories
+            .session_repository()
+            .find(&mut executor, session_id)
+            .await?
+            .filter(|session| session.account == account)
+            .ok_or(AppError::NotFound("session not found".to_string()))?;
+
+        self.revoke_session(&mut executor, &session.session_id)
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn auth_events(
+        &self,
+        auth_member: &AuthMemberDto,
+        dto: AuthEventQueryDto,
+    ) -> Result<AuthEventPageDto, AppError> {
+        let mut executor = self.pool.acquire().await?;
+
+        let member = self
+            .repositories
+            .member_repository()
+            .find(&mut executor, &auth_member.account)
+            .await?
+            .ok_or(AppError::NotFound("member not found".to_string()))?;
+
+        // events of a deleted account with the same name are not shown to its new owner
+        let mut query = dto.normalize();
+        query.account = Some(member.account);
+        query.from = query.from.max(member.created_at);
+
+        self.query_auth_events(&mut executor, query).await
+    }
+
+    pub async fn search_auth_events(
+        &self,
+        dto: AuthEventQueryDto,
+    ) -> Result<AuthEventPageDto, AppError> {
+        let mut executor = self.pool.acquire().await?;
+
+        self.query_auth_events(&mut executor, dto.normalize()).await
+    }
+
+    async fn query_auth_events(
+        &self,
+        executor: &mut DbExecutor,
+        query: AuthEventQueryDto,
+    ) -> Result<AuthEventPageDto, AppError> {
+        let filter = query.to_filter();
+
+        let total_count = self
+            .repositories
+            .auth_event_repository()
+            .count(executor, &filter)
+            .await?;
+
+        let events = self
+            .repositories
+            .auth_event_repository()
+            .list(executor, &filter, query.page, query.size)
+            .await?;
+
+        Ok(AuthEventPageDto::new(
+            events.into_iter().map(AuthEventDto::from_entity).collect(),
+            &query,
+            total_count,
+        ))
+    }
+
+    pub async fn create_access_token(
+        &self,
+        auth_member: &AuthMemberDto,
+        dto: AccessTokenCreateDto,
+    ) -> Result<AccessTokenDto, AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let token = format!("{}{}", ACCESS_TOKEN_PREFIX, generate_token(32)?);
+        let entity = self
+            .repositories
+            .access_token_repository()
+            .create(
+                &mut executor,
+                AccessTokenEntity::new(
+                    &generate_token(16)?,
+                    &auth_member.account,
+                    &dto.name,
+                    &hash_token(&token),
+                    &dto.scopes,
+                    dto.expired_at(),
+                ),
+            )
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(AccessTokenDto::from_entity(entity).with_token(&token))
+    }
+
+    pub async fn access_tokens(
+        &self,
+        auth_member: &AuthMemberDto,
+    ) -> Result<Vec<AccessTokenDto>, AppError> {
+        let mut executor = self.pool.acquire().await?;
+
+        let tokens = self
+            .repositories
+            .access_token_repository()
+            .list(&mut executor, &auth_member.account)
+            .await?;
+
+        Ok(tokens
+            .into_iter()
+            .map(AccessTokenDto::from_entity)
+            .collect())
+    }
+
+    pub async fn remove_access_token(&self, account: &str, token_id: &str) -> Result<(), AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let token = self
+            .repositories
+            .access_token_repository()
+            .find(&mut executor, token_id)
+            .await?
+            .filter(|token| token.account == account)
+            .ok_or(AppError::NotFound("access token not found".to_string()))?;
+
+        self.repositories
+            .access_token_repository()
+            .delete(&mut executor, &token.token_id)
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn authenticate_access_token(
+        &self,
+        token: &str,
+        client: ClientInfoDto,
+    ) -> Result<AuthMemberDto, AppError> {
+        let mut executor = self.pool.acquire().await?;
+
+        let token = self
+            .repositories
+            .access_token_repository()
+            .find_by_hash(&mut executor, &hash_token(token))
+            .await?;
+
+        // unknown and revoked tokens have no owner left, they are recorded without an account
+        let account = token
+            .as_ref()
+            .map(|token| token.account.clone())
+            .unwrap_or_default();
+
+        let result = self.authenticate_token(&mut executor, token).await;
+        if let Err(AppError::Unauthorized(_)) = &result {
+            self.record_event(
+                &mut executor,
+                &account,
+                AuthEventType::TokenRejected,
+                &client,
+            )
+            .await?;
+        }
+        result
+    }
+
+    async fn authenticate_token(
+        &self,
+        executor: &mut DbExecutor,
+        token: Option<AccessTokenEntity>,
+    ) -> Result<AuthMemberDto, AppError> {
+        let token = token
+            .filter(|token| !token.is_expired())
+            .ok_or(AppError::Unauthorized("invalid token".to_string()))?;
+
+        if token.is_stale(SESSION_TOUCH_SECONDS) {
+            self.repositories
+                .access_token_repository()
+                .touch(&mut *executor, &token.token_id)
+                .await?;
+        }
+
+        let member = self
+            .repositories
+            .member_repository()
+            .find(&mut *executor, &token.account)
+            .await?
+            .filter(|member| member.is_active())
+            .ok_or(AppError::Unauthorized("invalid token".to_string()))?;
+
+        Ok(AuthMemberDto::from_access_token(member, token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commons::config::{RECOVERY_CODE_COUNT, REGISTRATION_MODE, RegistrationMode};
+    use crate::commons::crypto::hash_token;
+    use crate::commons::error::AppError;
+    use crate::commons::mail::{LogMailSender, MemoryMailSender};
+    use crate::commons::password;
+    use crate::commons::setup;
+    use crate::commons::totp;
+    use crate::models::dtos::access_token::AccessTokenCreateDto;
+    use crate::models::dtos::auth::{
+        EmailVerificationConfirmDto, EmailVerificationRequestDto, PasswordDto,
+        PasswordResetConfirmDto, PasswordResetRequestDto, RefreshDto, SecondFactorDto, SigninDto,
+        SigninResultDto, SignupDto, TotpCodeDto, TotpDisableDto,
+    };
+    use crate::models::dtos::auth_event::AuthEventQueryDto;
+    use crate::models::dtos::session::ClientInfoDto;
+    use crate::models::entities::access_token::{AccessTokenEntity, Scope};
+    use crate::models::entities::auth_event::{AuthEventFilter, AuthEventType};
+    use crate::models::entities::member::{MemberStatus, Role};
+    use crate::repositories::interfaces::{
+        access_token::AccessTokenRepository, auth::AuthRepository, auth_event::AuthEventRepository,
+        member::MemberRepository, totp::TotpRepository,
+    };
+    use crate::repositories::{Repositories, RepositoriesExt};
+    use crate::use_cases::auth::AuthUseCases;
+
+    use chrono::Utc;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_auth_use_cases() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(
+            pool.clone(),
+            Arc::new(repositories),
+            Arc::new(LogMailSender),
+        );
+
+        let account = "account".to_string();
+        let password = "password".to_string();
+
+        let signup_dto = SignupDto {
+            account: account.clone(),
+            password: password.clone(),
+            confirm_password: password.clone(),
+            name: None,
+            email: None,
+        };
+
+        let result = use_cases.signup(signup_dto.clone()).await;
+        assert!(result.is_ok());
+
+        let result = use_cases.signup(signup_dto.clone()).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+
+        let signin_dto = SigninDto {
+            account: account.clone(),
+            password: password.clone(),
+        };
+
+        let result = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let token = result.unwrap().into_token().unwrap().token;
+
+        let result = use_cases
+            .authenticate(&token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let auth_member_dto = result.unwrap();
+
+        assert_eq!(auth_member_dto.account, account.clone());
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_pwd_lock() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(
+            pool.clone(),
+            Arc::new(repositories),
+            Arc::new(LogMailSender),
+        );
+
+        let account = "account".to_string();
+        let password = "password".to_string();
+
+        let signup_dto = SignupDto {
+            account: account.clone(),
+            password: password.clone(),
+            confirm_password: password.clone(),
+            name: None,
+            email: None,
+        };
+
+        let result = use_cases.signup(signup_dto.clone()).await;
+        assert!(result.is_ok());
+
+        let miss_password = "missmatch".to_string();
+
+        let signin_dto = SigninDto {
+            account: account.clone(),
+            password: miss_password.clone(),
+        };
+
+        let result = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert!(result.is_err());
+
+        let result = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert!(result.is_err());
+
+        let result = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        // a wrong password does not reveal the lock
+        let result = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert_eq!(result.unwrap_err().detail(), "invalid credentials");
+
+        let unknown_dto = SigninDto {
+            account: "unknown".to_string(),
+            password: password.clone(),
+        };
+
+        let result = use_cases
+            .signin(unknown_dto, ClientInfoDto::default())
+            .await;
+        assert_eq!(result.unwrap_err().detail(), "invalid credentials");
+
+        let signin_dto = SigninDto {
+            account: account.clone(),
+            password: password.clone(),
+        };
+
+        let result = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Locked(_))));
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_refresh() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(
+            pool.clone(),
+            Arc::new(repositories),
+            Arc::new(LogMailSender),
+        );
+
+        let account = "account".to_string();
+        let password = "password".to_string();
+
+        let signup_dto = SignupDto {
+            account: account.clone(),
+            password: password.clone(),
+            confirm_password: password.clone(),
+            name: None,
+            email: None,
+        };
+
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+
+        let signin_dto = SigninDto {
+            account: account.clone(),
+            password: password.clone(),
+        };
+
+        let first = use_cases
+            .signin(signin_dto, ClientInfoDto::default())
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+
+        let refresh_dto = RefreshDto {
+            refresh_token: first.refresh_token.clone(),
+        };
+
+        let result = use_cases
+            .refresh(refresh_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let second = result.unwrap();
+        assert_ne!(second.refresh_token, first.refresh_token);
+
+        let result = use_cases
+            .authenticate(&first.token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_err());
+
+        let result = use_cases
+            .authenticate(&second.token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases
+            .refresh(refresh_dto, ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let result = use_cases
+            .authenticate(&second.token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_err());
+
+        let refresh_dto = RefreshDto {
+            refresh_token: second.refresh_token,
+        };
+
+        let result = use_cases
+            .refresh(refresh_dto, ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_signout_revokes_refresh() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(
+            pool.clone(),
+            Arc::new(repositories),
+            Arc::new(LogMailSender),
+        );
+
+        let signup_dto = SignupDto {
+            account: "account".to_string(),
+            password: "password".to_string(),
+            confirm_password: "password".to_string(),
+            name: None,
+            email: None,
+        };
+
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+
+        let signin_dto = SigninDto {
+            account: "account".to_string(),
+            password: "password".to_string(),
+        };
+
+        let token = use_cases
+            .signin(signin_dto, ClientInfoDto::default())
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+
+        let auth_member = use_cases
+            .authenticate(&token.token, ClientInfoDto::default())
+            .await
+            .unwrap();
+
+        let result = use_cases
+            .signout(&auth_member, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases
+            .authenticate(&token.token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_err());
+
+        let refresh_dto = RefreshDto {
+            refresh_token: token.refresh_token,
+        };
+
+        let result = use_cases
+            .refresh(refresh_dto, ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_sessions() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(
+            pool.clone(),
+            Arc::new(repositories),
+            Arc::new(LogMailSender),
+        );
+
+        let signup_dto = SignupDto {
+            account: "account".to_string(),
+            password: "password".to_string(),
+            confirm_password: "password".to_string(),
+            name: None,
+            email: None,
+        };
+
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+
+        let signin_dto = SigninDto {
+            account: "account".to_string(),
+            password: "password".to_string(),
+        };
+
+        let laptop = ClientInfoDto {
+            user_agent: Some("laptop".to_string()),
+            ip_address: Some("192.0.2.1".to_string()),
+        };
+        let phone = ClientInfoDto {
+            user_agent: Some("phone".to_string()),
+            ip_address: Some("192.0.2.2".to_string()),
+        };
+
+        let laptop_token = use_cases
+            .signin(signin_dto.clone(), laptop)
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+        let phone_token = use_cases
+            .signin(signin_dto.clone(), phone)
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+
+        let laptop_member = use_cases
+            .authenticate(&laptop_token.token, ClientInfoDto::default())
+            .await
+            .unwrap();
+        let phone_member = use_cases
+            .authenticate(&phone_token.token, ClientInfoDto::default())
+            .await
+            .unwrap();
+        assert_ne!(laptop_member.session_id, phone_member.session_id);
+
+        let sessions = use_cases.sessions(&laptop_member).await.unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions.iter().filter(|session| session.current).count(), 1);
+        assert!(
+            sessions
+                .iter()
+                .any(|session| session.user_agent == Some("phone".to_string()))
+        );
+
+        let result = use_cases
+            .remove_session("other", &phone_member.session_id)
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = use_cases
+            .remove_session("account", &phone_member.session_id)
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases
+            .authenticate(&phone_token.token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_err());
+
+        let result = use_cases
+            .authenticate(&laptop_token.token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let refresh_dto = RefreshDto {
+            refresh_token: phone_token.refresh_token,
         };
+        let result = use_cases
+            .refresh(refresh_dto, ClientInfoDto::default())
+            .await;
+        assert!(result.is_err());
+
+        let result = use_cases.signin(signin_dto, ClientInfoDto::default()).await;
+        assert!(result.is_ok());
+
+        let result = use_cases
+            .signout_all("account", ClientInfoDto::default())
+            .await;
+        assert_eq!(result.unwrap(), 2);
 
-        let result = use_cases.signin(signin_dto.clone()).await;
+        let result = use_cases
+            .authenticate(&laptop_token.token, ClientInfoDto::default())
+            .await;
         assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_member_status() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Arc::new(Repositories::new());
+        let use_cases =
+            AuthUseCases::new(pool.clone(), repositories.clone(), Arc::new(LogMailSender));
+
+        for account in ["first", "second"] {
+            let signup_dto = SignupDto {
+                account: account.to_string(),
+                password: "password".to_string(),
+                confirm_password: "password".to_string(),
+                name: None,
+                email: None,
+            };
+            let result = use_cases.signup(signup_dto).await;
+            assert!(result.is_ok());
+
+            let status = result.unwrap();
+            match (account, *REGISTRATION_MODE) {
+                ("second", RegistrationMode::Approval) => {
+                    assert_eq!(status, MemberStatus::Pending)
+                }
+                _ => assert_eq!(status, MemberStatus::Active),
+            }
+        }
+
+        let signin_dto = SigninDto {
+            account: "first".to_string(),
+            password: "password".to_string(),
+        };
+
+        let token = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+
+        let auth_member = use_cases
+            .authenticate(&token.token, ClientInfoDto::default())
+            .await
+            .unwrap();
+        assert_eq!(auth_member.role, Role::Admin);
+
+        let mut executor = pool.acquire().await.unwrap();
+        let result = repositories
+            .member_repository()
+            .update_status(&mut executor, "first", MemberStatus::Suspended)
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases
+            .authenticate(&token.token, ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let result = use_cases.signin(signin_dto, ClientInfoDto::default()).await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let refresh_dto = RefreshDto {
+            refresh_token: token.refresh_token,
+        };
+        let result = use_cases
+            .refresh(refresh_dto, ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_change_password() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Arc::new(Repositories::new());
+        let use_cases =
+            AuthUseCases::new(pool.clone(), repositories.clone(), Arc::new(LogMailSender));
+
+        let signup_dto = SignupDto {
+            account: "account".to_string(),
+            password: "password1".to_string(),
+            confirm_password: "password1".to_string(),
+            name: None,
+            email: None,
+        };
+
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+
+        let signin_dto = SigninDto {
+            account: "account".to_string(),
+            password: "password1".to_string(),
+        };
+
+        let current = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+        let other = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+
+        let auth_member = use_cases
+            .authenticate(&current.token, ClientInfoDto::default())
+            .await
+            .unwrap();
+
+        let password_dto = PasswordDto {
+            current_password: "wrong".to_string(),
+            password: "password2".to_string(),
+            confirm_password: "password2".to_string(),
+        };
+        let result = use_cases
+            .change_password(&auth_member, password_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        // a wrong current password counts toward the lockout and is logged
+        let mut executor = pool.acquire().await.unwrap();
+        let auth = repositories
+            .auth_repository()
+            .find(&mut executor, "account")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(auth.missmatch, 1);
+        drop(executor);
+
+        let events = use_cases
+            .auth_events(&auth_member, AuthEventQueryDto::default())
+            .await
+            .unwrap();
+        assert_eq!(events.items[0].event_type, AuthEventType::ReauthFailed);
 
-        let result = use_cases.signin(signin_dto.clone()).await;
+        let password_dto = PasswordDto {
+            current_password: "password1".to_string(),
+            ..password_dto
+        };
+        let result = use_cases
+            .change_password(&auth_member, password_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases
+            .authenticate(&current.token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases
+            .authenticate(&other.token, ClientInfoDto::default())
+            .await;
         assert!(result.is_err());
 
-        let result = use_cases.signin(signin_dto.clone()).await;
+        let refresh_dto = RefreshDto {
+            refresh_token: other.refresh_token,
+        };
+        let result = use_cases
+            .refresh(refresh_dto, ClientInfoDto::default())
+            .await;
         assert!(result.is_err());
 
+        let result = use_cases.signin(signin_dto, ClientInfoDto::default()).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
         let signin_dto = SigninDto {
-            account: account.clone(),
-            password: password.clone(),
+            account: "account".to_string(),
+            password: "password2".to_string(),
         };
+        let result = use_cases.signin(signin_dto, ClientInfoDto::default()).await;
+        assert!(result.is_ok());
 
-        let result = use_cases.signin(signin_dto.clone()).await;
-        assert!(result.is_err());
+        // once locked, even the right password cannot be confirmed
+        for _ in 0..3 {
+            let result = use_cases
+                .change_password(&auth_member, password_dto.clone(), ClientInfoDto::default())
+                .await;
+            assert!(matches!(result, Err(AppError::Validation(_))));
+        }
+        let password_dto = PasswordDto {
+            current_password: "password2".to_string(),
+            ..password_dto
+        };
+        let result = use_cases
+            .change_password(&auth_member, password_dto, ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Locked(_))));
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_password_reset() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let mailer = Arc::new(MemoryMailSender::default());
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(pool.clone(), Arc::new(repositories), mailer.clone());
+
+        let signup_dto = SignupDto {
+            account: "account".to_string(),
+            password: "password1".to_string(),
+            confirm_password: "password1".to_string(),
+            name: None,
+            email: Some("account@local".to_string()),
+        };
+
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+        mailer.mails.lock().unwrap().clear();
+
+        let signin_dto = SigninDto {
+            account: "account".to_string(),
+            password: "wrong".to_string(),
+        };
+        for _ in 0..3 {
+            let result = use_cases
+                .signin(signin_dto.clone(), ClientInfoDto::default())
+                .await;
+            assert!(result.is_err());
+        }
+
+        let request_dto = PasswordResetRequestDto {
+            account: "unknown".to_string(),
+        };
+        let result = use_cases.request_password_reset(request_dto).await;
+        assert!(result.is_ok());
+        assert!(mailer.mails.lock().unwrap().is_empty());
+
+        let request_dto = PasswordResetRequestDto {
+            account: "account".to_string(),
+        };
+        let result = use_cases.request_password_reset(request_dto).await;
+        assert!(result.is_ok());
+
+        let mail = mailer.mails.lock().unwrap().pop().unwrap();
+        assert_eq!(mail.to, "account@local".to_string());
+
+        let token = mail
+            .body
+            .lines()
+            .find(|line| line.len() == 64)
+            .unwrap()
+            .to_string();
+
+        let confirm_dto = PasswordResetConfirmDto {
+            token: "invalid".to_string(),
+            password: "password2".to_string(),
+            confirm_password: "password2".to_string(),
+        };
+        let result = use_cases.confirm_password_reset(confirm_dto.clone()).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let confirm_dto = PasswordResetConfirmDto {
+            token: token.clone(),
+            ..confirm_dto
+        };
+        let result = use_cases.confirm_password_reset(confirm_dto.clone()).await;
+        assert!(result.is_ok());
+
+        let result = use_cases.confirm_password_reset(confirm_dto).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let signin_dto = SigninDto {
+            account: "account".to_string(),
+            password: "password2".to_string(),
+        };
+        let result = use_cases.signin(signin_dto, ClientInfoDto::default()).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_email_verification() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let mailer = Arc::new(MemoryMailSender::default());
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(pool.clone(), Arc::new(repositories), mailer.clone());
+
+        let signup_dto = SignupDto {
+            account: "account".to_string(),
+            password: "password1".to_string(),
+            confirm_password: "password1".to_string(),
+            name: None,
+            email: Some("account@local".to_string()),
+        };
+
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+
+        let mail = mailer.mails.lock().unwrap().pop().unwrap();
+        assert_eq!(mail.to, "account@local".to_string());
+        assert_eq!(mail.subject, "Email verification".to_string());
+
+        let request_dto = EmailVerificationRequestDto {
+            account: "account".to_string(),
+        };
+        let result = use_cases.request_email_verification(request_dto).await;
+        assert!(result.is_ok());
+
+        let token = mailer
+            .mails
+            .lock()
+            .unwrap()
+            .pop()
+            .unwrap()
+            .body
+            .lines()
+            .find(|line| line.len() == 64)
+            .unwrap()
+            .to_string();
+
+        let stale = mail
+            .body
+            .lines()
+            .find(|line| line.len() == 64)
+            .unwrap()
+            .to_string();
+        let confirm_dto = EmailVerificationConfirmDto { token: stale };
+        let result = use_cases.confirm_email_verification(confirm_dto).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let confirm_dto = EmailVerificationConfirmDto { token };
+        let result = use_cases
+            .confirm_email_verification(confirm_dto.clone())
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases.confirm_email_verification(confirm_dto).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let signin_dto = SigninDto {
+            account: "account".to_string(),
+            password: "password1".to_string(),
+        };
+        let token = use_cases
+            .signin(signin_dto, ClientInfoDto::default())
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+
+        let auth_member = use_cases
+            .authenticate(&token.token, ClientInfoDto::default())
+            .await
+            .unwrap();
+        assert!(auth_member.email_verified_at.is_some());
+
+        let request_dto = EmailVerificationRequestDto {
+            account: "account".to_string(),
+        };
+        let result = use_cases.request_email_verification(request_dto).await;
+        assert!(result.is_ok());
+        assert!(mailer.mails.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_access_token() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(
+            pool.clone(),
+            Arc::new(repositories.clone()),
+            Arc::new(LogMailSender),
+        );
+
+        let signup_dto = SignupDto {
+            account: "account".to_string(),
+            password: "password".to_string(),
+            confirm_password: "password".to_string(),
+            name: None,
+            email: None,
+        };
+
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+
+        let signin_dto = SigninDto {
+            account: "account".to_string(),
+            password: "password".to_string(),
+        };
+        let token = use_cases
+            .signin(signin_dto, ClientInfoDto::default())
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+        let auth_member = use_cases
+            .authenticate(&token.token, ClientInfoDto::default())
+            .await
+            .unwrap();
+
+        let create_dto = AccessTokenCreateDto {
+            name: "ci".to_string(),
+            scopes: vec![Scope::ContentRead],
+            expires_in_days: Some(30),
+        };
+        let result = use_cases
+            .create_access_token(&auth_member, create_dto)
+            .await;
+        assert!(result.is_ok());
+
+        let created = result.unwrap();
+        let access_token = created.token.clone().unwrap();
+        assert!(access_token.starts_with("pat_"));
+
+        let result = use_cases.access_tokens(&auth_member).await;
+        let result = result.unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(result[0].token.is_none());
+
+        let result = use_cases
+            .authenticate_access_token(&access_token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert_eq!(result.account, "account".to_string());
+        assert_eq!(result.scopes, Some(vec![Scope::ContentRead]));
+
+        let result = use_cases
+            .authenticate(&token.token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let client = ClientInfoDto {
+            ip_address: Some("192.0.2.1".to_string()),
+            user_agent: Some("ci".to_string()),
+        };
+        let result = use_cases
+            .authenticate_access_token("pat_unknown", client.clone())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let result = use_cases
+            .remove_access_token("other", &created.token_id)
+            .await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = use_cases
+            .remove_access_token("account", &created.token_id)
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases
+            .authenticate_access_token(&access_token, client.clone())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let expired = AccessTokenEntity::new(
+            "expired",
+            "account",
+            "expired",
+            &hash_token("pat_expired"),
+            &[Scope::ContentRead],
+            Some(Utc::now() - chrono::Duration::days(1)),
+        );
+        repositories
+            .access_token_repository()
+            .create(&mut pool.acquire().await.unwrap(), expired)
+            .await
+            .unwrap();
+        let result = use_cases
+            .authenticate_access_token("pat_expired", client.clone())
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let filter = AuthEventFilter {
+            event_type: Some(AuthEventType::TokenRejected),
+            ..Default::default()
+        };
+        let events = repositories
+            .auth_event_repository()
+            .list_all(&mut pool.acquire().await.unwrap(), &filter)
+            .await
+            .unwrap();
+        let mut accounts: Vec<_> = events.iter().map(|event| event.account.as_str()).collect();
+        accounts.sort();
+        assert_eq!(accounts, vec!["", "", "account"]);
+        assert!(
+            events
+                .iter()
+                .all(|event| event.ip_address == Some("192.0.2.1".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_totp() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(
+            pool.clone(),
+            Arc::new(repositories.clone()),
+            Arc::new(LogMailSender),
+        );
+
+        let signup_dto = SignupDto {
+            account: "account".to_string(),
+            password: "password1".to_string(),
+            confirm_password: "password1".to_string(),
+            name: None,
+            email: None,
+        };
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+
+        let signin_dto = SigninDto {
+            account: "account".to_string(),
+            password: "password1".to_string(),
+        };
+        let token = use_cases
+            .signin(signin_dto.clone(), ClientInfoDto::default())
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+        let auth_member = use_cases
+            .authenticate(&token.token, ClientInfoDto::default())
+            .await
+            .unwrap();
+
+        let result = use_cases.enroll_totp(&auth_member).await;
+        assert!(result.is_ok());
+
+        let enrollment = use_cases.enroll_totp(&auth_member).await.unwrap();
+        assert!(enrollment.otpauth_uri.starts_with("otpauth://totp/"));
+        let secret = enrollment.secret;
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let code_dto = TotpCodeDto {
+            code: totp::generate(&secret, now).unwrap(),
+        };
+        let result = use_cases.confirm_totp(&auth_member, code_dto).await;
+        let recovery_codes = result.unwrap().recovery_codes;
+        assert_eq!(recovery_codes.len(), *RECOVERY_CODE_COUNT);
+
+        let result = use_cases.enroll_totp(&auth_member).await;
+        assert!(matches!(result, Err(AppError::Conflict(_))));
+
+        let signin = |code: String, challenge_token: String| SecondFactorDto {
+            challenge_token,
+            code,
+        };
+        let challenge = || async {
+            match use_cases
+                .signin(signin_dto.clone(), ClientInfoDto::default())
+                .await
+                .unwrap()
+            {
+                SigninResultDto::SecondFactor(challenge) => challenge.challenge_token,
+                SigninResultDto::Token(_) => panic!("expected second factor challenge"),
+            }
+        };
+
+        let challenge_token = challenge().await;
+        let result = use_cases
+            .verify_second_factor(
+                signin("invalid".to_string(), challenge_token.clone()),
+                ClientInfoDto::default(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let result = use_cases
+            .verify_second_factor(
+                signin(recovery_codes[0].clone(), challenge_token.clone()),
+                ClientInfoDto::default(),
+            )
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases
+            .verify_second_factor(
+                signin(recovery_codes[1].clone(), challenge_token),
+                ClientInfoDto::default(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let challenge_token = challenge().await;
+        let result = use_cases
+            .verify_second_factor(
+                signin(recovery_codes[0].clone(), challenge_token.clone()),
+                ClientInfoDto::default(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let code = totp::generate(&secret, now + 30).unwrap();
+        let result = use_cases
+            .verify_second_factor(signin(code, challenge_token), ClientInfoDto::default())
+            .await;
+        let token = result.unwrap();
+        let result = use_cases
+            .authenticate(&token.token, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let challenge_token = challenge().await;
+        for _ in 0..3 {
+            let result = use_cases
+                .verify_second_factor(
+                    signin("invalid".to_string(), challenge_token.clone()),
+                    ClientInfoDto::default(),
+                )
+                .await;
+            assert!(matches!(result, Err(AppError::Unauthorized(_))));
+        }
+        let result = use_cases
+            .verify_second_factor(
+                signin(recovery_codes[1].clone(), challenge_token),
+                ClientInfoDto::default(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Locked(_))));
+
+        // a locked account cannot turn off its second factor
+        let disable_dto = TotpDisableDto {
+            password: "password1".to_string(),
+        };
+        let result = use_cases
+            .disable_totp(&auth_member, disable_dto.clone(), ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Locked(_))));
+
+        let mut executor = pool.acquire().await.unwrap();
+        let mut auth = repositories
+            .auth_repository()
+            .find(&mut executor, "account")
+            .await
+            .unwrap()
+            .unwrap();
+        auth.unlock();
+        repositories
+            .auth_repository()
+            .update(&mut executor, auth)
+            .await
+            .unwrap();
+        drop(executor);
+
+        let wrong_dto = TotpDisableDto {
+            password: "password2".to_string(),
+        };
+        let result = use_cases
+            .disable_totp(&auth_member, wrong_dto, ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let mut executor = pool.acquire().await.unwrap();
+        let auth = repositories
+            .auth_repository()
+            .find(&mut executor, "account")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(auth.missmatch, 1);
+        drop(executor);
+
+        let result = use_cases
+            .disable_totp(&auth_member, disable_dto, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let mut executor = pool.acquire().await.unwrap();
+        let result = repositories
+            .totp_repository()
+            .find(&mut executor, "account")
+            .await;
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_auth_events() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = AuthUseCases::new(
+            pool.clone(),
+            Arc::new(repositories),
+            Arc::new(LogMailSender),
+        );
+
+        let signup_dto = SignupDto {
+            account: "account".to_string(),
+            password: "password".to_string(),
+            confirm_password: "password".to_string(),
+            name: None,
+            email: None,
+        };
+
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+
+        let client = ClientInfoDto {
+            user_agent: Some("laptop".to_string()),
+            ip_address: Some("192.0.2.1".to_string()),
+        };
+
+        let result = use_cases
+            .signin(
+                SigninDto {
+                    account: "account".to_string(),
+                    password: "wrong".to_string(),
+                },
+                client.clone(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let result = use_cases
+            .signin(
+                SigninDto {
+                    account: "unknown".to_string(),
+                    password: "password".to_string(),
+                },
+                client.clone(),
+            )
+            .await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let token = use_cases
+            .signin(
+                SigninDto {
+                    account: "account".to_string(),
+                    password: "password".to_string(),
+                },
+                client.clone(),
+            )
+            .await
+            .unwrap()
+            .into_token()
+            .unwrap();
+
+        let auth_member = use_cases
+            .authenticate(&token.token, client.clone())
+            .await
+            .unwrap();
+
+        let result = use_cases.signout(&auth_member, client.clone()).await;
+        assert!(result.is_ok());
+
+        let result = use_cases.authenticate(&token.token, client.clone()).await;
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let result = use_cases
+            .auth_events(&auth_member, AuthEventQueryDto::default())
+            .await
+            .unwrap();
+        assert_eq!(result.total_count, 4);
+        let event_types: Vec<_> = result.items.iter().map(|event| event.event_type).collect();
+        assert_eq!(
+            event_types,
+            vec![
+                AuthEventType::TokenRejected,
+                AuthEventType::Signout,
+                AuthEventType::Signin,
+                AuthEventType::SigninFailed,
+            ]
+        );
+        assert_eq!(result.items[0].ip_address, Some("192.0.2.1".to_string()));
+        assert_eq!(result.items[0].user_agent, Some("laptop".to_string()));
+
+        // members cannot widen the query to other accounts
+        let query = AuthEventQueryDto {
+            account: Some("unknown".to_string()),
+            ..Default::default()
+        };
+        let result = use_cases.auth_events(&auth_member, query).await.unwrap();
+        assert_eq!(result.total_count, 4);
+
+        let query = AuthEventQueryDto {
+            event_type: Some(AuthEventType::SigninFailed),
+            ..Default::default()
+        };
+        let result = use_cases.search_auth_events(query).await.unwrap();
+        assert_eq!(result.total_count, 2);
+
+        let query = AuthEventQueryDto {
+            account: Some("unknown".to_string()),
+            ..Default::default()
+        };
+        let result = use_cases.search_auth_events(query).await.unwrap();
+        assert_eq!(result.total_count, 1);
+
+        let query = AuthEventQueryDto {
+            page: i32::MAX,
+            size: i32::MAX,
+            ..Default::default()
+        };
+        let result = use_cases.auth_events(&auth_member, query).await.unwrap();
+        assert!(result.items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_auth_use_cases_rehash() {
+        use argon2::password_hash::{PasswordHasher, SaltString};
+
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Arc::new(Repositories::new());
+        let use_cases =
+            AuthUseCases::new(pool.clone(), repositories.clone(), Arc::new(LogMailSender));
+
+        let signup_dto = SignupDto {
+            account: "account".to_string(),
+            password: "password".to_string(),
+            confirm_password: "password".to_string(),
+            name: None,
+            email: None,
+        };
+
+        let result = use_cases.signup(signup_dto).await;
+        assert!(result.is_ok());
+
+        let weak = argon2::Argon2::new(
+            argon2::Algorithm::Argon2id,
+            argon2::Version::V0x13,
+            argon2::Params::new(16, 1, 1, None).unwrap(),
+        )
+        .hash_password(b"password", &SaltString::encode_b64(&[1u8; 16]).unwrap())
+        .unwrap()
+        .to_string();
+        assert!(password::needs_rehash(&weak));
+
+        let mut executor = pool.acquire().await.unwrap();
+        let result = repositories
+            .member_repository()
+            .update_password(&mut executor, "account", &weak)
+            .await;
+        assert_eq!(result.unwrap(), 1);
+
+        let result = use_cases
+            .signin(
+                SigninDto {
+                    account: "account".to_string(),
+                    password: "password".to_string(),
+                },
+                ClientInfoDto::default(),
+            )
+            .await;
+        assert!(result.is_ok());
+
+        let member = repositories
+            .member_repository()
+            .find(&mut executor, "account")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_ne!(member.password, weak);
+        assert!(!password::needs_rehash(&member.password));
+        assert!(
+            password::verify("password".to_string(), member.password)
+                .await
+                .unwrap()
+        );
     }
 }
@@ -1,5 +1,8 @@
-use crate::commons::types::{BoxError, DbPool};
-use crate::models::dtos::content::ContentDto;
+use crate::commons::config::EMAIL_VERIFICATION;
+use crate::commons::error::AppError;
+use crate::commons::types::DbPool;
+use crate::models::dtos::content::{ContentDto, ContentPageDto, ContentQueryDto};
+use crate::models::dtos::member::AuthMemberDto;
 use crate::repositories::RepositoriesExt;
 use crate::repositories::interfaces::content::ContentRepository;
 use derive_new::new;
@@ -12,13 +15,26 @@ pub struct ContentUseCases<R: RepositoriesExt> {
 }
 
 impl<R: RepositoriesExt> ContentUseCases<R> {
-    pub async fn post(&self, dto: ContentDto) -> Result<ContentDto, BoxError> {
+    pub async fn post(
+        &self,
+        auth_member: &AuthMemberDto,
+        dto: ContentDto,
+    ) -> Result<ContentDto, AppError> {
+        if !EMAIL_VERIFICATION.allows_post(auth_member.email_verified_at.is_some()) {
+            return Err(AppError::Forbidden(
+                "email address is not verified".to_string(),
+            ));
+        }
+
+        let mut entity = dto.to_entity();
+        entity.account = auth_member.account.clone();
+
         let mut executor = self.pool.begin().await?;
 
         let content = self
             .repositories
             .content_repository()
-            .create(&mut *executor, dto.to_entity())
+            .create(&mut executor, entity)
             .await?;
 
         executor.commit().await?;
@@ -26,59 +42,126 @@ impl<R: RepositoriesExt> ContentUseCases<R> {
         Ok(ContentDto::from_entity(content))
     }
 
-    pub async fn get(&self, content_id: i64) -> Result<Option<ContentDto>, BoxError> {
+    pub async fn get(&self, content_id: i64) -> Result<Option<ContentDto>, AppError> {
         let mut executor = self.pool.acquire().await?;
 
         let content = self
             .repositories
             .content_repository()
-            .find(&mut *executor, content_id)
+            .find(&mut executor, content_id)
             .await?;
 
-        Ok(match content {
-            Some(content) => Some(ContentDto::from_entity(content)),
-            None => None,
-        })
+        Ok(content.map(ContentDto::from_entity))
     }
 
-    pub async fn edit(&self, dto: ContentDto) -> Result<ContentDto, BoxError> {
+    pub async fn edit(
+        &self,
+        auth_member: &AuthMemberDto,
+        dto: ContentDto,
+    ) -> Result<ContentDto, AppError> {
         let mut executor = self.pool.begin().await?;
 
+        let current = self
+            .repositories
+            .content_repository()
+            .find(&mut executor, dto.content_id)
+            .await?
+            .ok_or(AppError::NotFound("content not found".to_string()))?;
+
+        if !auth_member.can_edit(&current.account) {
+            return Err(AppError::Forbidden(
+                "not the owner of the content".to_string(),
+            ));
+        }
+
+        let mut entity = dto.to_entity();
+        entity.account = current.account;
+
         let content = self
             .repositories
             .content_repository()
-            .update(&mut *executor, dto.to_entity())
+            .update(&mut executor, entity)
             .await?
-            .ok_or(sqlx::Error::RowNotFound)?;
+            .ok_or(AppError::NotFound("content not found".to_string()))?;
 
         executor.commit().await?;
 
         Ok(ContentDto::from_entity(content))
     }
 
-    pub async fn remove(&self, content_id: i64) -> Result<u64, BoxError> {
+    pub async fn remove(
+        &self,
+        auth_member: &AuthMemberDto,
+        content_id: i64,
+    ) -> Result<u64, AppError> {
         let mut executor = self.pool.begin().await?;
 
+        let current = self
+            .repositories
+            .content_repository()
+            .find(&mut executor, content_id)
+            .await?
+            .ok_or(AppError::NotFound("content not found".to_string()))?;
+
+        if !auth_member.can_edit(&current.account) {
+            return Err(AppError::Forbidden(
+                "not the owner of the content".to_string(),
+            ));
+        }
+
         let count = self
             .repositories
             .content_repository()
-            .delete(&mut *executor, content_id)
+            .delete(&mut executor, content_id)
             .await?;
 
         if count == 0 {
-            return Err(sqlx::Error::RowNotFound.into());
+            return Err(AppError::NotFound("content not found".to_string()));
         }
 
         executor.commit().await?;
 
         Ok(count)
     }
+
+    pub async fn list(&self, dto: ContentQueryDto) -> Result<ContentPageDto, AppError> {
+        let query = dto.normalize();
+
+        let mut executor = self.pool.acquire().await?;
+
+        let total_count = self
+            .repositories
+            .content_repository()
+            .count(&mut executor, query.title.as_deref())
+            .await?;
+
+        let contents = self
+            .repositories
+            .content_repository()
+            .list(
+                &mut executor,
+                query.title.as_deref(),
+                query.page,
+                query.size,
+            )
+            .await?;
+
+        Ok(ContentPageDto::new(
+            contents.into_iter().map(ContentDto::from_entity).collect(),
+            query,
+            total_count,
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::commons::error::AppError;
     use crate::commons::setup;
-    use crate::models::dtos::content::ContentDto;
+    use crate::models::dtos::content::{ContentDto, ContentQueryDto};
+    use crate::models::dtos::member::AuthMemberDto;
+    use crate::models::dtos::pagination::MAX_PAGE;
+    use crate::models::entities::member::Role;
     use crate::repositories::Repositories;
     use crate::use_cases::content::ContentUseCases;
     use std::sync::Arc;
@@ -98,25 +181,163 @@ mod tests {
             body: "body".to_string(),
         };
 
-        let result = use_cases.post(dto.clone()).await;
+        let auth_member = AuthMemberDto {
+            account: "account".to_string(),
+            ..Default::default()
+        };
+
+        let result = use_cases.post(&auth_member, dto.clone()).await;
         assert!(result.is_ok());
 
         let dto = result.unwrap();
 
-        let result = use_cases.get(dto.content_id.clone()).await;
+        let result = use_cases.get(dto.content_id).await;
         assert!(result.is_ok());
 
         let mut dto = result.unwrap().unwrap();
         dto.title = "title2".to_string();
         dto.body = "body2".to_string();
 
-        let result = use_cases.edit(dto.clone()).await;
+        let result = use_cases.edit(&auth_member, dto.clone()).await;
         assert!(result.is_ok());
 
-        let result = use_cases.remove(dto.content_id.clone()).await;
+        let result = use_cases.remove(&auth_member, dto.content_id).await;
         assert!(result.is_ok());
 
         let count = result.unwrap();
         assert_eq!(count, 1);
     }
+
+    #[tokio::test]
+    async fn test_content_use_cases_list() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = ContentUseCases::new(pool.clone(), Arc::new(repositories));
+
+        let auth_member = AuthMemberDto {
+            account: "account".to_string(),
+            ..Default::default()
+        };
+
+        for i in 0..15 {
+            let dto = ContentDto {
+                content_id: 0,
+                account: "account".to_string(),
+                post_at: chrono::Utc::now(),
+                title: if i % 3 == 0 { "news" } else { "diary" }.to_string(),
+                body: "body".to_string(),
+            };
+            let result = use_cases.post(&auth_member, dto).await;
+            assert!(result.is_ok());
+        }
+
+        let query = ContentQueryDto {
+            page: 2,
+            size: 10,
+            title: None,
+        };
+        let result = use_cases.list(query).await;
+        assert!(result.is_ok());
+
+        let page = result.unwrap();
+        assert_eq!(page.items.len(), 5);
+        assert_eq!(page.total_count, 15);
+        assert_eq!(page.total_pages, 2);
+
+        let query = ContentQueryDto {
+            page: 0,
+            size: 1000,
+            title: Some("new".to_string()),
+        };
+        let result = use_cases.list(query).await;
+        assert!(result.is_ok());
+
+        let page = result.unwrap();
+        assert_eq!(page.page, 1);
+        assert_eq!(page.items.len(), 5);
+        assert_eq!(page.total_count, 5);
+        assert!(page.items.iter().all(|item| item.title == "news"));
+
+        let query = ContentQueryDto {
+            page: i32::MAX,
+            size: 100,
+            title: None,
+        };
+        let result = use_cases.list(query).await;
+        assert!(result.is_ok());
+
+        let page = result.unwrap();
+        assert_eq!(page.page, MAX_PAGE);
+        assert!(page.items.is_empty());
+        assert!(page.next.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_content_use_cases_ownership() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Repositories::new();
+        let use_cases = ContentUseCases::new(pool.clone(), Arc::new(repositories));
+
+        let dto = ContentDto {
+            content_id: 0,
+            account: "someone".to_string(),
+            post_at: chrono::Utc::now(),
+            title: "title".to_string(),
+            body: "body".to_string(),
+        };
+
+        let owner = AuthMemberDto {
+            account: "owner".to_string(),
+            ..Default::default()
+        };
+
+        let result = use_cases.post(&owner, dto.clone()).await;
+        assert!(result.is_ok());
+
+        let dto = result.unwrap();
+        assert_eq!(dto.account, "owner".to_string());
+
+        let other = AuthMemberDto {
+            account: "other".to_string(),
+            ..Default::default()
+        };
+        let editor = AuthMemberDto {
+            account: "editor".to_string(),
+            role: Role::Editor,
+            ..Default::default()
+        };
+
+        let mut edit_dto = dto.clone();
+        edit_dto.title = "hijacked".to_string();
+
+        let result = use_cases.edit(&other, edit_dto.clone()).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), AppError::Forbidden(_)));
+
+        let result = use_cases.remove(&other, dto.content_id).await;
+        assert!(result.is_err());
+        assert!(matches!(result.unwrap_err(), AppError::Forbidden(_)));
+
+        let result = use_cases.get(dto.content_id).await;
+        assert_eq!(result.unwrap(), Some(dto.clone()));
+
+        edit_dto.account = "other".to_string();
+        let result = use_cases.edit(&owner, edit_dto.clone()).await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().account, "owner".to_string());
+
+        edit_dto.title = "moderated".to_string();
+        let result = use_cases.edit(&editor, edit_dto).await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert_eq!(result.account, "owner".to_string());
+        assert_eq!(result.title, "moderated".to_string());
+
+        let result = use_cases.remove(&owner, dto.content_id).await;
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), 1);
+    }
 }
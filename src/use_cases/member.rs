@@ -0,0 +1,850 @@
+use crate::commons::config::{
+    ACCOUNT_DELETION_CONTENT, ContentDeletionPolicy, EMAIL_VERIFICATION, EmailVerificationPolicy,
+    TOMBSTONE_ACCOUNT,
+};
+use crate::commons::error::AppError;
+use crate::commons::types::{DbExecutor, DbPool};
+use crate::commons::validation::FieldError;
+use crate::models::dtos::auth::LockStatusDto;
+use crate::models::dtos::member::{
+    AccountDeletionDto, AuthMemberDto, MemberDto, MemberExportDto, ProfileDto, PublicMemberDto,
+};
+use crate::models::dtos::session::ClientInfoDto;
+use crate::models::entities::audit_log::AuditLogEntity;
+use crate::models::entities::auth_event::AuthEventFilter;
+use crate::models::entities::member::{MemberEntity, MemberStatus, Role};
+use crate::repositories::RepositoriesExt;
+use crate::repositories::interfaces::{
+    access_token::AccessTokenRepository, audit_log::AuditLogRepository, auth::AuthRepository,
+    auth_event::AuthEventRepository, content::ContentRepository, member::MemberRepository,
+    one_time_token::OneTimeTokenRepository, recovery_code::RecoveryCodeRepository,
+    refresh_token::RefreshTokenRepository, session::SessionRepository, totp::TotpRepository,
+};
+use crate::use_cases::auth::AuthUseCases;
+use derive_new::new;
+use std::sync::Arc;
+
+#[derive(new, Clone)]
+pub struct MemberUseCases<R: RepositoriesExt> {
+    pool: DbPool,
+    repositories: Arc<R>,
+    auth: AuthUseCases<R>,
+}
+
+impl<R: RepositoriesExt> MemberUseCases<R> {
+    pub async fn profile(&self, account: &str) -> Result<PublicMemberDto, AppError> {
+        let mut executor = self.pool.acquire().await?;
+
+        let member = self
+            .repositories
+            .member_repository()
+            .find(&mut executor, account)
+            .await?
+            .filter(|member| member.is_active())
+            .ok_or(AppError::NotFound("member not found".to_string()))?;
+
+        Ok(PublicMemberDto::from_entity(member))
+    }
+
+    pub async fn update_profile(
+        &self,
+        auth_member: &AuthMemberDto,
+        dto: ProfileDto,
+    ) -> Result<MemberDto, AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        let mut member = self.find(&mut executor, &auth_member.account).await?;
+
+        let email_changed = dto.apply(&mut member);
+        if email_changed
+            && member.email.is_none()
+            && *EMAIL_VERIFICATION != EmailVerificationPolicy::Optional
+        {
+            return Err(AppError::Validation(vec![FieldError::new(
+                "email",
+                "must not be empty",
+            )]));
+        }
+
+        let member = self
+            .repositories
+            .member_repository()
+            .update(&mut executor, member)
+            .await?
+            .ok_or(AppError::NotFound("member not found".to_string()))?;
+
+        let verification = match (email_changed, &member.email) {
+            (true, Some(email)) => Some((
+                email.clone(),
+                self.auth
+                    .issue_email_verification(&mut executor, &member.account)
+                    .await?,
+            )),
+            _ => None,
+        };
+
+        executor.commit().await?;
+
+        if let Some((email, token)) = verification {
+            self.auth
+                .send_email_verification(&member.account, &email, &token)
+                .await;
+        }
+
+        Ok(MemberDto::from_entity(member))
+    }
+
+    pub async fn export(&self, auth_member: &AuthMemberDto) -> Result<MemberExportDto, AppError> {
+        let mut executor = self.pool.acquire().await?;
+
+        let member = self.find(&mut executor, &auth_member.account).await?;
+
+        let auth = self
+            .repositories
+            .auth_repository()
+            .find(&mut executor, &auth_member.account)
+            .await?;
+
+        // same rule as the event history, nothing from a deleted account with the same name
+        let filter = AuthEventFilter {
+            account: Some(member.account.clone()),
+            from: member.created_at,
+            ..Default::default()
+        };
+        let auth_events = self
+            .repositories
+            .auth_event_repository()
+            .list_all(&mut executor, &filter)
+            .await?;
+
+        let contents = self
+            .repositories
+            .content_repository()
+            .list_by_account(&mut executor, &auth_member.account)
+            .await?;
+
+        Ok(MemberExportDto::new(member, auth, auth_events, contents))
+    }
+
+    pub async fn pending(&self) -> Result<Vec<MemberDto>, AppError> {
+        let mut executor = self.pool.acquire().await?;
+
+        let members = self
+            .repositories
+            .member_repository()
+            .list_by_status(&mut executor, MemberStatus::Pending)
+            .await?;
+
+        Ok(members.into_iter().map(MemberDto::from_entity).collect())
+    }
+
+    pub async fn approve(&self, account: &str) -> Result<MemberDto, AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        self.find_pending(&mut executor, account).await?;
+
+        let member = self
+            .repositories
+            .member_repository()
+            .update_status(&mut executor, account, MemberStatus::Active)
+            .await?
+            .ok_or(AppError::NotFound("pending member not found".to_string()))?;
+
+        executor.commit().await?;
+
+        Ok(MemberDto::from_entity(member))
+    }
+
+    pub async fn reject(&self, account: &str) -> Result<(), AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        self.find_pending(&mut executor, account).await?;
+
+        self.remove_account(&mut executor, account).await?;
+
+        executor.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn withdraw(
+        &self,
+        auth_member: &AuthMemberDto,
+        dto: AccountDeletionDto,
+        client: ClientInfoDto,
+    ) -> Result<(), AppError> {
+        self.auth
+            .reauthenticate(&auth_member.account, dto.password, "password", &client)
+            .await?;
+
+        let mut executor = self.pool.begin().await?;
+
+        self.find(&mut executor, &auth_member.account).await?;
+
+        self.remove_account(&mut executor, &auth_member.account)
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(&self, auth_member: &AuthMemberDto, account: &str) -> Result<(), AppError> {
+        if auth_member.account == account {
+            return Err(AppError::Forbidden(
+                "use /members/me to delete own account".to_string(),
+            ));
+        }
+
+        let mut executor = self.pool.begin().await?;
+
+        self.find(&mut executor, account).await?;
+
+        let contents = self.remove_account(&mut executor, account).await?;
+
+        self.repositories
+            .audit_log_repository()
+            .create(
+                &mut executor,
+                AuditLogEntity::new(
+                    &auth_member.account,
+                    "delete",
+                    account,
+                    Some(serde_json::json!({ "contents": contents }).to_string()),
+                ),
+            )
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn change_role(
+        &self,
+        auth_member: &AuthMemberDto,
+        account: &str,
+        role: Role,
+    ) -> Result<MemberDto, AppError> {
+        if auth_member.account == account {
+            return Err(AppError::Forbidden("cannot change own role".to_string()));
+        }
+
+        let mut executor = self.pool.begin().await?;
+
+        let member = self
+            .repositories
+            .member_repository()
+            .update_role(&mut executor, account, role)
+            .await?
+            .ok_or(AppError::NotFound("member not found".to_string()))?;
+
+        executor.commit().await?;
+
+        Ok(MemberDto::from_entity(member))
+    }
+
+    pub async fn lock_status(&self, account: &str) -> Result<LockStatusDto, AppError> {
+        let mut executor = self.pool.acquire().await?;
+
+        self.find(&mut executor, account).await?;
+
+        let auth = self
+            .repositories
+            .auth_repository()
+            .find(&mut executor, account)
+            .await?;
+
+        Ok(LockStatusDto::from_entity(account, auth))
+    }
+
+    pub async fn unlock(
+        &self,
+        auth_member: &AuthMemberDto,
+        account: &str,
+    ) -> Result<LockStatusDto, AppError> {
+        let mut executor = self.pool.begin().await?;
+
+        self.find(&mut executor, account).await?;
+
+        let auth = self
+            .repositories
+            .auth_repository()
+            .find(&mut executor, account)
+            .await?;
+
+        let before = LockStatusDto::from_entity(account, auth.clone());
+
+        let auth = match auth {
+            Some(mut auth) => {
+                auth.unlock();
+                self.repositories
+                    .auth_repository()
+                    .update(&mut executor, auth)
+                    .await?
+            }
+            None => None,
+        };
+
+        self.repositories
+            .audit_log_repository()
+            .create(
+                &mut executor,
+                AuditLogEntity::new(
+                    &auth_member.account,
+                    "unlock",
+                    account,
+                    serde_json::to_string(&before).ok(),
+                ),
+            )
+            .await?;
+
+        executor.commit().await?;
+
+        Ok(LockStatusDto::from_entity(account, auth))
+    }
+
+    // removes everything tied to the account and returns the number of deleted or reassigned contents
+    async fn remove_account(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+    ) -> Result<u64, AppError> {
+        let contents = match *ACCOUNT_DELETION_CONTENT {
+            ContentDeletionPolicy::Delete => {
+                self.repositories
+                    .content_repository()
+                    .delete_by_account(executor, account)
+                    .await?
+            }
+            ContentDeletionPolicy::Reassign => {
+                self.repositories
+                    .content_repository()
+                    .reassign(executor, account, &TOMBSTONE_ACCOUNT)
+                    .await?
+            }
+        };
+
+        self.repositories
+            .session_repository()
+            .delete_by_account(executor, account)
+            .await?;
+
+        self.repositories
+            .refresh_token_repository()
+            .delete_by_account(executor, account)
+            .await?;
+
+        self.repositories
+            .access_token_repository()
+            .delete_by_account(executor, account)
+            .await?;
+
+        self.repositories
+            .one_time_token_repository()
+            .delete_all_by_account(executor, account)
+            .await?;
+
+        self.repositories
+            .totp_repository()
+            .delete(executor, account)
+            .await?;
+
+        self.repositories
+            .recovery_code_repository()
+            .delete_by_account(executor, account)
+            .await?;
+
+        self.repositories
+            .auth_repository()
+            .delete(executor, account)
+            .await?;
+
+        self.repositories
+            .member_repository()
+            .delete(executor, account)
+            .await?;
+
+        Ok(contents)
+    }
+
+    async fn find(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+    ) -> Result<MemberEntity, AppError> {
+        self.repositories
+            .member_repository()
+            .find(executor, account)
+            .await?
+            .ok_or(AppError::NotFound("member not found".to_string()))
+    }
+
+    async fn find_pending(
+        &self,
+        executor: &mut DbExecutor,
+        account: &str,
+    ) -> Result<MemberEntity, AppError> {
+        self.repositories
+            .member_repository()
+            .find(executor, account)
+            .await?
+            .filter(|member| member.status == MemberStatus::Pending)
+            .ok_or(AppError::NotFound("pending member not found".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commons::error::AppError;
+    use crate::commons::mail::{LogMailSender, MemoryMailSender};
+    use crate::commons::setup;
+    use crate::commons::types::DbPool;
+    use crate::models::dtos::member::{AccountDeletionDto, AuthMemberDto, ProfileDto};
+    use crate::models::dtos::session::ClientInfoDto;
+    use crate::models::entities::auth::AuthEntity;
+    use crate::models::entities::auth_event::{AuthEventEntity, AuthEventType};
+    use crate::models::entities::content::ContentEntity;
+    use crate::models::entities::member::{MemberEntity, MemberStatus, Role};
+    use crate::repositories::interfaces::{
+        audit_log::AuditLogRepository, auth::AuthRepository, auth_event::AuthEventRepository,
+        content::ContentRepository, member::MemberRepository,
+    };
+    use crate::repositories::{Repositories, RepositoriesExt};
+    use crate::use_cases::auth::AuthUseCases;
+    use crate::use_cases::member::MemberUseCases;
+    use std::sync::Arc;
+
+    fn member_use_cases(
+        pool: &DbPool,
+        repositories: Arc<Repositories>,
+    ) -> MemberUseCases<Repositories> {
+        let auth = AuthUseCases::new(pool.clone(), repositories.clone(), Arc::new(LogMailSender));
+        MemberUseCases::new(pool.clone(), repositories, auth)
+    }
+
+    #[tokio::test]
+    async fn test_member_use_cases_approval() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Arc::new(Repositories::new());
+        let use_cases = member_use_cases(&pool, repositories.clone());
+
+        let mut executor = pool.acquire().await.unwrap();
+        for (account, status) in [
+            ("admin", MemberStatus::Active),
+            ("approved", MemberStatus::Pending),
+            ("rejected", MemberStatus::Pending),
+        ] {
+            let entity = MemberEntity {
+                account: account.to_string(),
+                password: "password".to_string(),
+                name: None,
+                email: None,
+                email_verified_at: None,
+                status,
+                role: Role::Member,
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repositories
+                .member_repository()
+                .create(&mut executor, entity)
+                .await;
+            assert!(result.is_ok());
+        }
+
+        let result = use_cases.pending().await;
+        assert_eq!(result.unwrap().len(), 2);
+
+        let result = use_cases.approve("approved").await;
+        assert_eq!(result.unwrap().status, MemberStatus::Active);
+
+        let result = use_cases.approve("approved").await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = use_cases.reject("admin").await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = use_cases.reject("rejected").await;
+        assert!(result.is_ok());
+
+        let result = use_cases.pending().await;
+        assert!(result.unwrap().is_empty());
+
+        let result = repositories
+            .member_repository()
+            .find(&mut executor, "rejected")
+            .await;
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_member_use_cases_change_role() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Arc::new(Repositories::new());
+        let use_cases = member_use_cases(&pool, repositories.clone());
+
+        let mut executor = pool.acquire().await.unwrap();
+        for (account, role) in [("admin", Role::Admin), ("member", Role::Member)] {
+            let entity = MemberEntity {
+                account: account.to_string(),
+                password: "password".to_string(),
+                name: None,
+                email: None,
+                email_verified_at: None,
+                status: MemberStatus::Active,
+                role,
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repositories
+                .member_repository()
+                .create(&mut executor, entity)
+                .await;
+            assert!(result.is_ok());
+        }
+
+        let admin = AuthMemberDto {
+            account: "admin".to_string(),
+            role: Role::Admin,
+            ..Default::default()
+        };
+
+        let result = use_cases.change_role(&admin, "member", Role::Editor).await;
+        assert_eq!(result.unwrap().role, Role::Editor);
+
+        let result = use_cases.change_role(&admin, "admin", Role::Member).await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let result = use_cases.change_role(&admin, "unknown", Role::Editor).await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_member_use_cases_unlock() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Arc::new(Repositories::new());
+        let use_cases = member_use_cases(&pool, repositories.clone());
+
+        let mut executor = pool.acquire().await.unwrap();
+        let entity = MemberEntity {
+            account: "locked".to_string(),
+            password: "password".to_string(),
+            name: None,
+            email: None,
+            email_verified_at: None,
+            status: MemberStatus::Active,
+            role: Role::Member,
+            created_at: None,
+            updated_at: None,
+        };
+        let result = repositories
+            .member_repository()
+            .create(&mut executor, entity)
+            .await;
+        assert!(result.is_ok());
+
+        let mut auth = AuthEntity::new_missmatched("locked".to_string());
+        auth.missmatched();
+        auth.missmatched();
+        auth.missmatched();
+        let result = repositories
+            .auth_repository()
+            .create(&mut executor, auth)
+            .await;
+        assert!(result.is_ok());
+
+        let result = use_cases.lock_status("locked").await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert!(result.locked);
+        assert_eq!(result.missmatch, 4);
+        assert!(result.unlock_at.is_some());
+
+        let admin = AuthMemberDto {
+            account: "admin".to_string(),
+            role: Role::Admin,
+            ..Default::default()
+        };
+
+        let result = use_cases.unlock(&admin, "locked").await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert!(!result.locked);
+        assert_eq!(result.missmatch, 0);
+        assert!(result.challenge_at.is_none());
+
+        let result = repositories
+            .audit_log_repository()
+            .list_by_target(&mut executor, "locked")
+            .await;
+        let result = result.unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].actor, "admin".to_string());
+        assert_eq!(result[0].action, "unlock".to_string());
+
+        let result = use_cases.lock_status("unknown").await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_member_use_cases_profile() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let mailer = Arc::new(MemoryMailSender::default());
+        let repositories = Arc::new(Repositories::new());
+        let auth = AuthUseCases::new(pool.clone(), repositories.clone(), mailer.clone());
+        let use_cases = MemberUseCases::new(pool.clone(), repositories.clone(), auth);
+
+        let mut executor = pool.acquire().await.unwrap();
+        for (account, status) in [
+            ("member", MemberStatus::Active),
+            ("pending", MemberStatus::Pending),
+        ] {
+            let entity = MemberEntity {
+                account: account.to_string(),
+                password: "password".to_string(),
+                name: Some(account.to_string()),
+                email: Some(format!("{}@local", account)),
+                email_verified_at: None,
+                status,
+                role: Role::Member,
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repositories
+                .member_repository()
+                .create(&mut executor, entity)
+                .await;
+            assert!(result.is_ok());
+        }
+
+        let result = use_cases.profile("member").await;
+        assert_eq!(result.unwrap().name, Some("member".to_string()));
+
+        let result = use_cases.profile("pending").await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let member = AuthMemberDto {
+            account: "member".to_string(),
+            ..Default::default()
+        };
+        let dto = ProfileDto {
+            name: Some("renamed".to_string()),
+            email: Some("".to_string()),
+        };
+
+        let result = use_cases.update_profile(&member, dto).await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert_eq!(result.name, Some("renamed".to_string()));
+        assert!(result.email.is_none());
+        assert!(result.updated_at.is_some());
+        assert!(mailer.mails.lock().unwrap().is_empty());
+
+        // a new address gets a verification token in the same transaction as the change
+        let dto = ProfileDto {
+            name: None,
+            email: Some("changed@local".to_string()),
+        };
+        let result = use_cases.update_profile(&member, dto.clone()).await;
+        assert!(result.unwrap().email_verified_at.is_none());
+
+        let mail = mailer.mails.lock().unwrap().pop().unwrap();
+        assert_eq!(mail.to, "changed@local".to_string());
+
+        let result = use_cases.update_profile(&member, dto).await;
+        assert!(result.is_ok());
+        assert!(mailer.mails.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_member_use_cases_delete() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Arc::new(Repositories::new());
+        let use_cases = member_use_cases(&pool, repositories.clone());
+
+        let mut executor = pool.acquire().await.unwrap();
+        for account in ["admin", "member", "other"] {
+            let entity = MemberEntity {
+                account: account.to_string(),
+                password: crate::commons::password::hash("password1".to_string())
+                    .await
+                    .unwrap(),
+                name: None,
+                email: None,
+                email_verified_at: None,
+                status: MemberStatus::Active,
+                role: Role::Member,
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repositories
+                .member_repository()
+                .create(&mut executor, entity)
+                .await;
+            assert!(result.is_ok());
+
+            let entity = ContentEntity {
+                content_id: 0,
+                account: account.to_string(),
+                post_at: chrono::Utc::now(),
+                title: "title".to_string(),
+                body: "body".to_string(),
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repositories
+                .content_repository()
+                .create(&mut executor, entity)
+                .await;
+            assert!(result.is_ok());
+        }
+
+        let member = AuthMemberDto {
+            account: "member".to_string(),
+            ..Default::default()
+        };
+
+        let dto = AccountDeletionDto {
+            password: "wrong".to_string(),
+        };
+        let result = use_cases
+            .withdraw(&member, dto, ClientInfoDto::default())
+            .await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        let result = repositories
+            .auth_repository()
+            .find(&mut executor, "member")
+            .await;
+        assert_eq!(result.unwrap().unwrap().missmatch, 1);
+
+        let dto = AccountDeletionDto {
+            password: "password1".to_string(),
+        };
+        let result = use_cases
+            .withdraw(&member, dto, ClientInfoDto::default())
+            .await;
+        assert!(result.is_ok());
+
+        let result = repositories
+            .member_repository()
+            .find(&mut executor, "member")
+            .await;
+        assert!(result.unwrap().is_none());
+
+        let result = repositories
+            .content_repository()
+            .count(&mut executor, None)
+            .await;
+        assert_eq!(result.unwrap(), 2);
+
+        let admin = AuthMemberDto {
+            account: "admin".to_string(),
+            role: Role::Admin,
+            ..Default::default()
+        };
+
+        let result = use_cases.delete(&admin, "admin").await;
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+
+        let result = use_cases.delete(&admin, "other").await;
+        assert!(result.is_ok());
+
+        let result = use_cases.delete(&admin, "other").await;
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+
+        let result = repositories
+            .audit_log_repository()
+            .list_by_target(&mut executor, "other")
+            .await;
+        assert_eq!(result.unwrap()[0].action, "delete".to_string());
+    }
+
+    #[tokio::test]
+    async fn test_member_use_cases_export() {
+        let pool = setup::initialize_db("sqlite::memory:").await.unwrap();
+
+        let repositories = Arc::new(Repositories::new());
+        let use_cases = member_use_cases(&pool, repositories.clone());
+
+        let mut executor = pool.acquire().await.unwrap();
+        for account in ["member", "other"] {
+            let entity = MemberEntity {
+                account: account.to_string(),
+                password: "password".to_string(),
+                name: Some(account.to_string()),
+                email: None,
+                email_verified_at: None,
+                status: MemberStatus::Active,
+                role: Role::Member,
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repositories
+                .member_repository()
+                .create(&mut executor, entity)
+                .await;
+            assert!(result.is_ok());
+
+            let entity = ContentEntity {
+                content_id: 0,
+                account: account.to_string(),
+                post_at: chrono::Utc::now(),
+                title: "title".to_string(),
+                body: "body".to_string(),
+                created_at: None,
+                updated_at: None,
+            };
+            let result = repositories
+                .content_repository()
+                .create(&mut executor, entity)
+                .await;
+            assert!(result.is_ok());
+        }
+
+        let result = repositories
+            .auth_repository()
+            .create(
+                &mut executor,
+                AuthEntity::new_missmatched("member".to_string()),
+            )
+            .await;
+        assert!(result.is_ok());
+
+        for account in ["member", "other"] {
+            let result = repositories
+                .auth_event_repository()
+                .create(
+                    &mut executor,
+                    AuthEventEntity::new(account, AuthEventType::SigninFailed, None, None),
+                )
+                .await;
+            assert!(result.is_ok());
+        }
+
+        let member = AuthMemberDto {
+            account: "member".to_string(),
+            ..Default::default()
+        };
+
+        let result = use_cases.export(&member).await;
+        assert!(result.is_ok());
+
+        let result = result.unwrap();
+        assert_eq!(result.member.account, "member".to_string());
+        assert_eq!(result.login.unwrap().missmatch, 1);
+        assert_eq!(result.auth_events.len(), 1);
+        assert_eq!(result.auth_events[0].account, "member".to_string());
+        assert_eq!(result.contents.len(), 1);
+        assert_eq!(result.contents[0].account, "member".to_string());
+    }
+}